@@ -0,0 +1,65 @@
+use naga::{Expression, Function, Handle, Module, SwizzleComponent, VectorSize};
+
+use crate::interpreter::{
+    value::{Scalar, Value},
+    Interpreter,
+};
+
+impl Interpreter {
+    /// Evaluates a dynamic index into a `u32` element index, rejecting
+    /// negative values.
+    pub(in crate::interpreter) fn index(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        index: Handle<Expression>,
+    ) -> anyhow::Result<u32> {
+        match self.expression(module, index, func)? {
+            Value::Scalar(Scalar::I32(index)) => u32::try_from(index)
+                .map_err(|_| anyhow::anyhow!("Index {} is out of bounds", index)),
+            Value::Scalar(Scalar::U32(index)) => Ok(index),
+            value => Err(anyhow::anyhow!(
+                "Invalid index: expected i32 or u32, got {:?}",
+                value.ty(module)
+            )),
+        }
+    }
+
+    /// Extracts element `index` of a composite value, or extends a pointer to
+    /// a composite to point at the element.
+    pub(super) fn access_index(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        base: Handle<Expression>,
+        index: u32,
+    ) -> anyhow::Result<Value> {
+        match self.expression(module, base, func)? {
+            Value::Pointer(mut pointer) => {
+                pointer.path.push(index);
+                Ok(Value::Pointer(pointer))
+            }
+            base => base.try_component(module, index),
+        }
+    }
+
+    pub(super) fn swizzle(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        size: VectorSize,
+        vector: Handle<Expression>,
+        pattern: [SwizzleComponent; 4],
+    ) -> anyhow::Result<Value> {
+        let vector = self.expression(module, vector, func)?;
+        let components = pattern[..size as usize]
+            .iter()
+            .map(|component| {
+                vector
+                    .try_component(module, *component as u32)?
+                    .try_scalar()
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Value::Vector(components))
+    }
+}
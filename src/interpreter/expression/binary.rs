@@ -1,307 +1,284 @@
-use naga::{BinaryOperator, Expression, Function, Handle, Module, TypeInner};
+use naga::{BinaryOperator, Expression, Function, Handle, Module};
 
-use crate::interpreter::{value::Value, Interpreter};
+use crate::interpreter::{
+    value::{Scalar, Value},
+    Interpreter,
+};
 
-macro_rules! binary {
-    ($result:ident; i32; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_mut::<i32>()?;
+macro_rules! integer_binary {
+    ($variant:ident, $divide:ident, $modulo:ident; $op:expr, $left:expr, $right:expr) => {
         match $op {
-            BinaryOperator::Add => *$result = $left + $right,
-            BinaryOperator::Subtract => *$result = $left - $right,
-            BinaryOperator::Multiply => *$result = $left * $right,
-            BinaryOperator::Divide => *$result = $left / $right,
-            BinaryOperator::Modulo => *$result = $left % $right,
-            BinaryOperator::And => *$result = $left & $right,
-            BinaryOperator::ExclusiveOr => *$result = $left ^ $right,
-            BinaryOperator::InclusiveOr => *$result = $left | $right,
-            BinaryOperator::ShiftLeft => *$result = $left << $right,
-            BinaryOperator::ShiftRight => *$result = $left >> $right,
-            _ => todo!("{:?}", $op),
-        }
-    };
-    ($result:ident; u32; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_mut::<u32>()?;
-        match $op {
-            BinaryOperator::Add => *$result = $left + $right,
-            BinaryOperator::Subtract => *$result = $left - $right,
-            BinaryOperator::Multiply => *$result = $left * $right,
-            BinaryOperator::Divide => *$result = $left / $right,
-            BinaryOperator::Modulo => *$result = $left % $right,
-            BinaryOperator::And => *$result = $left & $right,
-            BinaryOperator::ExclusiveOr => *$result = $left ^ $right,
-            BinaryOperator::InclusiveOr => *$result = $left | $right,
-            _ => todo!("{:?}", $op),
-        }
-    };
-    ($result:ident; f32; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_mut::<f32>()?;
-        match $op {
-            BinaryOperator::Add => *$result = $left + $right,
-            BinaryOperator::Subtract => *$result = $left - $right,
-            BinaryOperator::Multiply => *$result = $left * $right,
-            BinaryOperator::Divide => *$result = $left / $right,
-            _ => todo!("{:?}", $op),
-        }
-    };
-    ($result:ident; f64; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_mut::<f64>()?;
-        match $op {
-            BinaryOperator::Add => *$result = $left + $right,
-            BinaryOperator::Subtract => *$result = $left - $right,
-            BinaryOperator::Multiply => *$result = $left * $right,
-            BinaryOperator::Divide => *$result = $left / $right,
-            _ => todo!("{:?}", $op),
-        }
-    };
-    ($result:ident; bool; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_mut::<u8>()?;
-        match $op {
-            BinaryOperator::And => *$result = $left & $right,
-            BinaryOperator::ExclusiveOr => *$result = $left ^ $right,
-            BinaryOperator::InclusiveOr => *$result = $left | $right,
-            BinaryOperator::Equal => *$result = ($left == $right) as u8,
-            _ => todo!("{:?}", $op),
+            BinaryOperator::Add => Some(Scalar::$variant($left.wrapping_add($right))),
+            BinaryOperator::Subtract => Some(Scalar::$variant($left.wrapping_sub($right))),
+            BinaryOperator::Multiply => Some(Scalar::$variant($left.wrapping_mul($right))),
+            BinaryOperator::Divide => Some(Scalar::$variant($divide($left, $right))),
+            BinaryOperator::Modulo => Some(Scalar::$variant($modulo($left, $right))),
+            BinaryOperator::And => Some(Scalar::$variant($left & $right)),
+            BinaryOperator::ExclusiveOr => Some(Scalar::$variant($left ^ $right)),
+            BinaryOperator::InclusiveOr => Some(Scalar::$variant($left | $right)),
+            op => compare(op, $left, $right),
         }
     };
 }
 
-macro_rules! binary_offset {
-    ($result:ident; i32; $offset:expr; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_offset_mut::<i32>($offset)?;
-        match $op {
-            BinaryOperator::Add => *$result = $left + $right,
-            BinaryOperator::Subtract => *$result = $left - $right,
-            BinaryOperator::Multiply => *$result = $left * $right,
-            BinaryOperator::Divide => *$result = $left / $right,
-            BinaryOperator::Modulo => *$result = $left % $right,
-            BinaryOperator::And => *$result = $left & $right,
-            BinaryOperator::ExclusiveOr => *$result = $left ^ $right,
-            BinaryOperator::InclusiveOr => *$result = $left | $right,
-            BinaryOperator::ShiftLeft => *$result = $left << $right,
-            BinaryOperator::ShiftRight => *$result = $left >> $right,
-            _ => todo!("{:?}", $op),
-        }
-    };
-    ($result:ident; u32; $offset:expr; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_offset_mut::<u32>($offset)?;
-        match $op {
-            BinaryOperator::Add => *$result = $left + $right,
-            BinaryOperator::Subtract => *$result = $left - $right,
-            BinaryOperator::Multiply => *$result = $left * $right,
-            BinaryOperator::Divide => *$result = $left / $right,
-            BinaryOperator::Modulo => *$result = $left % $right,
-            BinaryOperator::And => *$result = $left & $right,
-            BinaryOperator::ExclusiveOr => *$result = $left ^ $right,
-            BinaryOperator::InclusiveOr => *$result = $left | $right,
-            _ => todo!("{:?}", $op),
-        }
-    };
-    ($result:ident; f32; $offset:expr; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_offset_mut::<f32>($offset)?;
-        match $op {
-            BinaryOperator::Add => *$result = $left + $right,
-            BinaryOperator::Subtract => *$result = $left - $right,
-            BinaryOperator::Multiply => *$result = $left * $right,
-            BinaryOperator::Divide => *$result = $left / $right,
-            _ => todo!("{:?}", $op),
-        }
-    };
-    ($result:ident; f64; $offset:expr; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_offset_mut::<f64>($offset)?;
-        match $op {
-            BinaryOperator::Add => *$result = $left + $right,
-            BinaryOperator::Subtract => *$result = $left - $right,
-            BinaryOperator::Multiply => *$result = $left * $right,
-            BinaryOperator::Divide => *$result = $left / $right,
-            _ => todo!("{:?}", $op),
-        }
-    };
-    ($result:ident; bool; $offset:expr; $op:expr, $left:expr, $right:expr) => {
-        let $result = $result.try_get_offset_mut::<u8>($offset)?;
+macro_rules! float_binary {
+    ($variant:ident; $op:expr, $left:expr, $right:expr) => {
         match $op {
-            BinaryOperator::And => *$result = $left & $right,
-            BinaryOperator::ExclusiveOr => *$result = $left ^ $right,
-            BinaryOperator::InclusiveOr => *$result = $left | $right,
-            BinaryOperator::Equal => *$result = ($left == $right) as u8,
-            _ => todo!("{:?}", $op),
+            BinaryOperator::Add => Some(Scalar::$variant($left + $right)),
+            BinaryOperator::Subtract => Some(Scalar::$variant($left - $right)),
+            BinaryOperator::Multiply => Some(Scalar::$variant($left * $right)),
+            BinaryOperator::Divide => Some(Scalar::$variant($left / $right)),
+            BinaryOperator::Modulo => Some(Scalar::$variant($left % $right)),
+            op => compare(op, $left, $right),
         }
     };
 }
 
-impl<'a> Interpreter<'a> {
+impl Interpreter {
     pub(super) fn binary(
         &mut self,
-        module: &'a Module,
+        module: &Module,
         func: &Function,
         op: naga::BinaryOperator,
         left: Handle<Expression>,
         right: Handle<Expression>,
-    ) -> anyhow::Result<Value<'a>> {
+    ) -> anyhow::Result<Value> {
         let left = self.expression(module, left, func)?;
+        if let BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr = op {
+            return self.logical(module, func, op, left, right);
+        }
         let right = self.expression(module, right, func)?;
-        let size = left.data.len();
-        if size != right.data.len() {
-            return Err(anyhow::anyhow!(
-                "Invalid binary expression: left size {}, right size {}",
-                left.data.len(),
-                right.data.len()
-            ));
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid binary expression: {:?} {:?} {:?}",
+                left.ty(module),
+                op,
+                right.ty(module)
+            )
+        };
+        if let (Value::Matrix(_), _) | (_, Value::Matrix(_)) = (&left, &right) {
+            return matrix_binary(op, &left, &right).ok_or_else(invalid);
         }
-        let mut result = Value::from_data(left.ty, vec![0; size]);
 
-        match (left.ty, right.ty) {
-            (
-                TypeInner::Scalar {
-                    kind: left_kind,
-                    width: left_width,
-                },
-                TypeInner::Scalar {
-                    kind: right_kind,
-                    width: right_width,
-                },
-            ) => {
-                if left_kind != right_kind {
-                    return Err(anyhow::anyhow!(
-                        "Invalid binary expression: left kind {:?}, right kind {:?}",
-                        left_kind,
-                        right_kind
-                    ));
-                }
-                if left_width != right_width {
-                    return Err(anyhow::anyhow!(
-                        "Invalid binary expression: left width {}, right width {}",
-                        left_width,
-                        right_width
-                    ));
-                }
+        let (Ok(left_components), Ok(right_components)) =
+            (left.try_components(), right.try_components())
+        else {
+            return Err(invalid());
+        };
+        // broadcast a scalar operand to the size of a vector operand
+        let size = match (&left, &right) {
+            (Value::Scalar(_), _) => right_components.len(),
+            (_, Value::Scalar(_)) => left_components.len(),
+            _ if left_components.len() == right_components.len() => left_components.len(),
+            _ => return Err(invalid()),
+        };
+        let component = |components: &[Scalar], i: usize| match *components {
+            [scalar] => scalar,
+            _ => components[i],
+        };
+        let result = (0..size)
+            .map(|i| {
+                let left = component(left_components, i);
+                let right = component(right_components, i);
+                scalar_binary(op, left, right).ok_or_else(invalid)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Value::from_components(result)
+    }
+}
 
-                match left_kind {
-                    naga::ScalarKind::Sint => match left_width {
-                        4 => {
-                            let left = left.try_get::<i32>()?;
-                            let right = right.try_get::<i32>()?;
-                            binary!(result; i32; op, *left, *right);
-                        }
-                        _ => todo!("{:?}", left_width),
-                    },
-                    naga::ScalarKind::Uint => match left_width {
-                        4 => {
-                            let left = left.try_get::<u32>()?;
-                            let right = right.try_get::<u32>()?;
-                            binary!(result; u32; op, *left, *right);
-                        }
-                        _ => todo!("{:?}", left_width),
-                    },
-                    naga::ScalarKind::Float => match left_width {
-                        4 => {
-                            let left = left.try_get::<f32>()?;
-                            let right = right.try_get::<f32>()?;
-                            binary!(result; f32; op, *left, *right);
-                        }
-                        8 => {
-                            let left = left.try_get::<f64>()?;
-                            let right = right.try_get::<f64>()?;
-                            binary!(result; f64; op, *left, *right);
-                        }
-                        _ => todo!("{:?}", left_width),
-                    },
-                    naga::ScalarKind::Bool => match left_width {
-                        1 => {
-                            let left = left.try_get::<u8>()?;
-                            let right = right.try_get::<u8>()?;
-                            binary!(result; bool; op, *left, *right);
-                        }
-                        _ => todo!("{:?}", left_width),
-                    },
-                }
+impl Interpreter {
+    /// Evaluates `&&` and `||`, only evaluating `right` if `left` does not
+    /// already determine the result.
+    fn logical(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        op: BinaryOperator,
+        left: Value,
+        right: Handle<Expression>,
+    ) -> anyhow::Result<Value> {
+        let invalid = |value: &Value| {
+            anyhow::anyhow!(
+                "Invalid logical expression: expected bool, got {:?}",
+                value.ty(module)
+            )
+        };
+        let Value::Scalar(Scalar::Bool(left)) = left else {
+            return Err(invalid(&left));
+        };
+        match (op, left) {
+            (BinaryOperator::LogicalAnd, false) | (BinaryOperator::LogicalOr, true) => {
+                return Ok(Value::Scalar(Scalar::Bool(left)));
             }
-            (
-                &TypeInner::Vector {
-                    size: left_size,
-                    kind: left_kind,
-                    width: left_width,
-                },
-                &TypeInner::Vector {
-                    size: right_size,
-                    kind: right_kind,
-                    width: right_width,
-                },
-            ) => {
-                if left_size != right_size {
-                    return Err(anyhow::anyhow!(
-                        "Invalid binary expression: left size {:?}, right size {:?}",
-                        left_size,
-                        right_size
-                    ));
-                }
-                if left_kind != right_kind {
-                    return Err(anyhow::anyhow!(
-                        "Invalid binary expression: left kind {:?}, right kind {:?}",
-                        left_kind,
-                        right_kind
-                    ));
-                }
-                if left_width != right_width {
-                    return Err(anyhow::anyhow!(
-                        "Invalid binary expression: left width {}, right width {}",
-                        left_width,
-                        right_width
-                    ));
-                }
+            _ => {}
+        }
+        match self.expression(module, right, func)? {
+            right @ Value::Scalar(Scalar::Bool(_)) => Ok(right),
+            right => Err(invalid(&right)),
+        }
+    }
+}
 
-                match left_kind {
-                    naga::ScalarKind::Sint => match left_width {
-                        4 => {
-                            for i in 0..left_size as usize {
-                                let left = left.try_get_offset::<i32>(i * 4)?;
-                                let right = right.try_get_offset::<i32>(i * 4)?;
-                                binary_offset!(result; i32; i * 4; op, *left, *right);
-                            }
-                        }
-                        _ => todo!("{:?}", left_width),
-                    },
-                    naga::ScalarKind::Uint => match left_width {
-                        4 => {
-                            for i in 0..left_size as usize {
-                                let left = left.try_get_offset::<u32>(i * 4)?;
-                                let right = right.try_get_offset::<u32>(i * 4)?;
-                                binary_offset!(result; u32; i * 4; op, *left, *right);
-                            }
-                        }
-                        _ => todo!("{:?}", left_width),
-                    },
-                    naga::ScalarKind::Float => match left_width {
-                        4 => {
-                            for i in 0..left_size as usize {
-                                let left = left.try_get_offset::<f32>(i * 4)?;
-                                let right = right.try_get_offset::<f32>(i * 4)?;
-                                binary_offset!(result; f32; i * 4; op, *left, *right);
-                            }
-                        }
-                        8 => {
-                            for i in 0..left_size as usize {
-                                let left = left.try_get_offset::<f64>(i * 8)?;
-                                let right = right.try_get_offset::<f64>(i * 8)?;
-                                binary_offset!(result; f64; i * 8; op, *left, *right);
-                            }
-                        }
-                        _ => todo!("{:?}", left_width),
-                    },
-                    naga::ScalarKind::Bool => match left_width {
-                        1 => {
-                            for i in 0..left_size as usize {
-                                let left = left.try_get_offset::<u8>(i)?;
-                                let right = right.try_get_offset::<u8>(i)?;
-                                binary_offset!(result; bool; i; op, *left, *right);
-                            }
-                        }
-                        _ => todo!("{:?}", left_width),
-                    },
-                }
-            }
-            _ => todo!("{:?} {:?}", left.ty, right.ty),
+/// Applies `op` to a pair of scalars, or returns `None` if it is not defined
+/// for them. Comparisons produce a `bool`.
+pub(in crate::interpreter) fn scalar_binary(
+    op: BinaryOperator,
+    left: Scalar,
+    right: Scalar,
+) -> Option<Scalar> {
+    match (op, left, right) {
+        (BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight, left, Scalar::U32(amount)) => {
+            shift(op, left, amount)
+        }
+        (_, Scalar::I32(left), Scalar::I32(right)) => {
+            integer_binary!(I32, divide_i32, modulo_i32; op, left, right)
         }
+        (_, Scalar::U32(left), Scalar::U32(right)) => {
+            integer_binary!(U32, divide_u32, modulo_u32; op, left, right)
+        }
+        (_, Scalar::F32(left), Scalar::F32(right)) => float_binary!(F32; op, left, right),
+        (_, Scalar::F64(left), Scalar::F64(right)) => float_binary!(F64; op, left, right),
+        (_, Scalar::Bool(left), Scalar::Bool(right)) => match op {
+            BinaryOperator::And => Some(Scalar::Bool(left & right)),
+            BinaryOperator::ExclusiveOr => Some(Scalar::Bool(left ^ right)),
+            BinaryOperator::InclusiveOr => Some(Scalar::Bool(left | right)),
+            BinaryOperator::Equal => Some(Scalar::Bool(left == right)),
+            BinaryOperator::NotEqual => Some(Scalar::Bool(left != right)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn compare<T: PartialOrd>(op: BinaryOperator, left: T, right: T) -> Option<Scalar> {
+    let result = match op {
+        BinaryOperator::Equal => left == right,
+        BinaryOperator::NotEqual => left != right,
+        BinaryOperator::Less => left < right,
+        BinaryOperator::LessEqual => left <= right,
+        BinaryOperator::Greater => left > right,
+        BinaryOperator::GreaterEqual => left >= right,
+        _ => return None,
+    };
+    Some(Scalar::Bool(result))
+}
+
+/// Integer division as defined by WGSL: dividing by zero yields the dividend,
+/// and `i32::MIN / -1` yields `i32::MIN`.
+fn divide_i32(left: i32, right: i32) -> i32 {
+    left.checked_div(right).unwrap_or(left)
+}
+
+fn divide_u32(left: u32, right: u32) -> u32 {
+    left.checked_div(right).unwrap_or(left)
+}
+
+/// Integer remainder as defined by WGSL: the remainder of dividing by zero, or
+/// of `i32::MIN / -1`, is zero.
+fn modulo_i32(left: i32, right: i32) -> i32 {
+    left.checked_rem(right).unwrap_or(0)
+}
 
-        Ok(result)
+fn modulo_u32(left: u32, right: u32) -> u32 {
+    left.checked_rem(right).unwrap_or(0)
+}
+
+/// Shifts an integer by a `u32` amount. As in WGSL, the amount is masked to
+/// the bit width of the shifted value.
+fn shift(op: BinaryOperator, left: Scalar, amount: u32) -> Option<Scalar> {
+    let amount = amount & 31;
+    match (left, op) {
+        (Scalar::I32(left), BinaryOperator::ShiftLeft) => Some(Scalar::I32(left << amount)),
+        (Scalar::I32(left), _) => Some(Scalar::I32(left >> amount)),
+        (Scalar::U32(left), BinaryOperator::ShiftLeft) => Some(Scalar::U32(left << amount)),
+        (Scalar::U32(left), _) => Some(Scalar::U32(left >> amount)),
+        _ => None,
+    }
+}
+
+/// Sum of the products of two equally long lists of floats.
+fn dot(left: &[Scalar], right: &[Scalar]) -> Option<Scalar> {
+    if left.len() != right.len() {
+        return None;
+    }
+    let mut products = left
+        .iter()
+        .zip(right)
+        .map(|(&left, &right)| scalar_binary(BinaryOperator::Multiply, left, right));
+    let first = products.next()??;
+    products.try_fold(first, |sum, product| {
+        scalar_binary(BinaryOperator::Add, sum, product?)
+    })
+}
+
+/// The rows of a matrix given as a list of columns.
+fn rows(columns: &[Vec<Scalar>]) -> Vec<Vec<Scalar>> {
+    (0..columns[0].len())
+        .map(|row| columns.iter().map(|column| column[row]).collect())
+        .collect()
+}
+
+/// Handles the operators that take a matrix operand: `mat * vec`, `vec * mat`,
+/// `mat * mat`, `mat * scalar`, `scalar * mat` and componentwise `mat ± mat`.
+/// Returns `None` if the operands do not fit the operator.
+fn matrix_binary(op: BinaryOperator, left: &Value, right: &Value) -> Option<Value> {
+    match (op, left, right) {
+        (
+            BinaryOperator::Add | BinaryOperator::Subtract,
+            Value::Matrix(left),
+            Value::Matrix(right),
+        ) => {
+            if left.len() != right.len() || left[0].len() != right[0].len() {
+                return None;
+            }
+            let columns = left
+                .iter()
+                .zip(right)
+                .map(|(left, right)| {
+                    left.iter()
+                        .zip(right)
+                        .map(|(&left, &right)| scalar_binary(op, left, right))
+                        .collect()
+                })
+                .collect::<Option<_>>()?;
+            Some(Value::Matrix(columns))
+        }
+        (BinaryOperator::Multiply, Value::Matrix(matrix), Value::Vector(vector)) => {
+            let result = rows(matrix)
+                .iter()
+                .map(|row| dot(row, vector))
+                .collect::<Option<_>>()?;
+            Some(Value::Vector(result))
+        }
+        (BinaryOperator::Multiply, Value::Vector(vector), Value::Matrix(matrix)) => {
+            let result = matrix
+                .iter()
+                .map(|column| dot(vector, column))
+                .collect::<Option<_>>()?;
+            Some(Value::Vector(result))
+        }
+        (BinaryOperator::Multiply, Value::Matrix(left), Value::Matrix(right)) => {
+            let rows = rows(left);
+            let columns = right
+                .iter()
+                .map(|column| rows.iter().map(|row| dot(row, column)).collect())
+                .collect::<Option<_>>()?;
+            Some(Value::Matrix(columns))
+        }
+        (BinaryOperator::Multiply, Value::Matrix(matrix), &Value::Scalar(scalar))
+        | (BinaryOperator::Multiply, &Value::Scalar(scalar), Value::Matrix(matrix)) => {
+            let columns = matrix
+                .iter()
+                .map(|column| {
+                    column
+                        .iter()
+                        .map(|&m| scalar_binary(op, m, scalar))
+                        .collect()
+                })
+                .collect::<Option<_>>()?;
+            Some(Value::Matrix(columns))
+        }
+        _ => None,
     }
 }
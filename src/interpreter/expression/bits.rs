@@ -0,0 +1,181 @@
+//! Helpers for the bit manipulation and data packing builtins.
+
+/// The lowest `count` bits set.
+fn mask(count: u32) -> u32 {
+    match count {
+        32 => u32::MAX,
+        count => (1 << count) - 1,
+    }
+}
+
+/// Clamps a bit range to the 32 bits of a component.
+fn bit_range(offset: u32, count: u32) -> (u32, u32) {
+    let offset = offset.min(32);
+    (offset, count.min(32 - offset))
+}
+
+pub fn first_leading_bit_u32(e: u32) -> u32 {
+    match e {
+        0 => u32::MAX,
+        e => 31 - e.leading_zeros(),
+    }
+}
+
+/// For negative values this finds the most significant zero bit instead.
+pub fn first_leading_bit_i32(e: i32) -> i32 {
+    let e = if e < 0 { !e } else { e };
+    match e {
+        0 => -1,
+        e => 31 - e.leading_zeros() as i32,
+    }
+}
+
+pub fn first_trailing_bit(e: u32) -> u32 {
+    match e {
+        0 => u32::MAX,
+        e => e.trailing_zeros(),
+    }
+}
+
+pub fn extract_bits_u32(e: u32, offset: u32, count: u32) -> u32 {
+    let (offset, count) = bit_range(offset, count);
+    match count {
+        0 => 0,
+        count => (e >> offset) & mask(count),
+    }
+}
+
+/// Like `extract_bits_u32`, but sign-extends the extracted bits.
+pub fn extract_bits_i32(e: i32, offset: u32, count: u32) -> i32 {
+    let (offset, count) = bit_range(offset, count);
+    match count {
+        0 => 0,
+        count => (e << (32 - offset - count)) >> (32 - count),
+    }
+}
+
+pub fn insert_bits(e: u32, newbits: u32, offset: u32, count: u32) -> u32 {
+    let (offset, count) = bit_range(offset, count);
+    match count {
+        0 => e,
+        count => {
+            let mask = mask(count) << offset;
+            (e & !mask) | ((newbits << offset) & mask)
+        }
+    }
+}
+
+/// Packs each component into `bits` bits of a `u32`, the first component in
+/// the least significant bits.
+fn pack(v: &[f64], bits: u32, f: impl Fn(f64) -> u32) -> u32 {
+    v.iter().enumerate().fold(0, |packed, (i, &x)| {
+        packed | (f(x) & mask(bits)) << (bits * i as u32)
+    })
+}
+
+/// Unpacks `count` components of `bits` bits each, the first component from
+/// the least significant bits.
+fn unpack(e: u32, count: u32, bits: u32, f: impl Fn(u32) -> f64) -> Vec<f64> {
+    (0..count)
+        .map(|i| f((e >> (bits * i)) & mask(bits)))
+        .collect()
+}
+
+/// As WGSL defines it, `floor(0.5 + 127 * clamp(e, -1, 1))`, so halves round
+/// towards positive infinity rather than away from zero.
+pub fn pack4x8snorm(v: &[f64]) -> u32 {
+    pack(v, 8, |x| {
+        (0.5 + x.clamp(-1.0, 1.0) * 127.0).floor() as i8 as u32
+    })
+}
+
+pub fn pack4x8unorm(v: &[f64]) -> u32 {
+    pack(v, 8, |x| (x.clamp(0.0, 1.0) * 255.0).round() as u32)
+}
+
+/// Like [`pack4x8snorm`], with `32767` in place of `127`.
+pub fn pack2x16snorm(v: &[f64]) -> u32 {
+    pack(v, 16, |x| {
+        (0.5 + x.clamp(-1.0, 1.0) * 32767.0).floor() as i16 as u32
+    })
+}
+
+pub fn pack2x16unorm(v: &[f64]) -> u32 {
+    pack(v, 16, |x| (x.clamp(0.0, 1.0) * 65535.0).round() as u32)
+}
+
+pub fn pack2x16float(v: &[f64]) -> u32 {
+    pack(v, 16, |x| f32_to_f16(x as f32) as u32)
+}
+
+pub fn unpack4x8snorm(e: u32) -> Vec<f64> {
+    unpack(e, 4, 8, |x| (x as u8 as i8 as f64 / 127.0).max(-1.0))
+}
+
+pub fn unpack4x8unorm(e: u32) -> Vec<f64> {
+    unpack(e, 4, 8, |x| x as f64 / 255.0)
+}
+
+pub fn unpack2x16snorm(e: u32) -> Vec<f64> {
+    unpack(e, 2, 16, |x| (x as u16 as i16 as f64 / 32767.0).max(-1.0))
+}
+
+pub fn unpack2x16unorm(e: u32) -> Vec<f64> {
+    unpack(e, 2, 16, |x| x as f64 / 65535.0)
+}
+
+pub fn unpack2x16float(e: u32) -> Vec<f64> {
+    unpack(e, 2, 16, |x| f16_to_f32(x as u16) as f64)
+}
+
+/// Converts to the bits of an IEEE 754 half-precision float, rounding to
+/// nearest even.
+fn f32_to_f16(x: f32) -> u16 {
+    let bits = x.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exp == 0xff {
+        let nan = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+
+    let round = |value: u32, shift: u32| {
+        let half = 1 << (shift - 1);
+        let rest = value & ((1 << shift) - 1);
+        let value = value >> shift;
+        if rest > half || (rest == half && value & 1 == 1) {
+            value + 1
+        } else {
+            value
+        }
+    };
+
+    let exp = exp - 127 + 15;
+    if exp >= 0x1f {
+        sign | 0x7c00
+    } else if exp <= 0 {
+        // too small for a normal half, so produce a subnormal or zero
+        if exp < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        sign | round(mantissa, (14 - exp) as u32) as u16
+    } else {
+        // a carry out of the mantissa correctly bumps the exponent
+        sign | round(((exp as u32) << 23) | mantissa, 13) as u16
+    }
+}
+
+fn f16_to_f32(h: u16) -> f32 {
+    let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = ((h >> 10) & 0x1f) as i32;
+    let mantissa = (h & 0x3ff) as f32;
+    match exp {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        exp => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exp - 15),
+    }
+}
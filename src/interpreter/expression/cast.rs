@@ -0,0 +1,86 @@
+use naga::{Bytes, Expression, Function, Handle, Module, ScalarKind};
+
+use crate::interpreter::{
+    value::{Scalar, Value},
+    Interpreter,
+};
+
+impl Scalar {
+    /// Converts to the given kind and width. Float to integer conversions
+    /// round toward zero and saturate, with NaN becoming zero.
+    fn convert(self, kind: ScalarKind, width: Bytes) -> anyhow::Result<Self> {
+        let result = match (kind, width) {
+            (ScalarKind::Bool, 1) => Self::Bool(match self {
+                Self::Bool(value) => value,
+                Self::I32(value) => value != 0,
+                Self::U32(value) => value != 0,
+                Self::F32(value) => value != 0.0,
+                Self::F64(value) => value != 0.0,
+            }),
+            (ScalarKind::Sint, 4) => Self::I32(match self {
+                Self::Bool(value) => value as i32,
+                Self::I32(value) => value,
+                Self::U32(value) => value as i32,
+                Self::F32(value) => value as i32,
+                Self::F64(value) => value as i32,
+            }),
+            (ScalarKind::Uint, 4) => Self::U32(match self {
+                Self::Bool(value) => value as u32,
+                Self::I32(value) => value as u32,
+                Self::U32(value) => value,
+                Self::F32(value) => value as u32,
+                Self::F64(value) => value as u32,
+            }),
+            (ScalarKind::Float, 4) => Self::F32(match self {
+                Self::Bool(value) => value as u8 as f32,
+                Self::I32(value) => value as f32,
+                Self::U32(value) => value as f32,
+                Self::F32(value) => value,
+                Self::F64(value) => value as f32,
+            }),
+            (ScalarKind::Float, 8) => Self::F64(match self {
+                Self::Bool(value) => value as u8 as f64,
+                Self::I32(value) => value as f64,
+                Self::U32(value) => value as f64,
+                Self::F32(value) => value as f64,
+                Self::F64(value) => value,
+            }),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Invalid conversion to {:?} of width {}",
+                    kind,
+                    width
+                ))
+            }
+        };
+        Ok(result)
+    }
+}
+
+impl Interpreter {
+    /// Evaluates `As`: a value conversion when `convert` is given, otherwise a
+    /// bitcast that keeps the width.
+    pub(super) fn cast(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        expr: Handle<Expression>,
+        kind: ScalarKind,
+        convert: Option<Bytes>,
+    ) -> anyhow::Result<Value> {
+        let value = self.expression(module, expr, func)?;
+        value.try_map(|scalar| match convert {
+            Some(width) => scalar.convert(kind, width),
+            None => {
+                if kind == ScalarKind::Bool || scalar.kind() == ScalarKind::Bool {
+                    return Err(anyhow::anyhow!(
+                        "Invalid bitcast of {:?} to {:?}",
+                        value.ty(module),
+                        kind
+                    ));
+                }
+                Scalar::from_bytes(kind, scalar.width(), &scalar.to_bytes())
+            }
+        })
+    }
+}
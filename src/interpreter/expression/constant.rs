@@ -0,0 +1,26 @@
+use naga::{Expression, Handle, Module};
+
+use crate::interpreter::value::Value;
+
+use super::{compose, literal};
+
+/// Evaluates an expression in `module.const_expressions`.
+pub fn const_expression(module: &Module, expr: Handle<Expression>) -> anyhow::Result<Value> {
+    match module.const_expressions[expr] {
+        Expression::Literal(lit) => Ok(literal(lit)),
+        Expression::Constant(constant) => const_expression(module, module.constants[constant].init),
+        Expression::ZeroValue(ty) => Value::zeroed(module, ty),
+        Expression::Compose { ty, ref components } => {
+            let components = components
+                .iter()
+                .map(|component| const_expression(module, *component))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            compose(module, ty, components)
+        }
+        Expression::Splat { size, value } => const_expression(module, value)?.try_splat(size),
+        ref expr => Err(anyhow::anyhow!(
+            "Unsupported constant expression: {:?}",
+            expr
+        )),
+    }
+}
@@ -0,0 +1,847 @@
+use naga::{
+    Bytes, Expression, Function, Handle, MathFunction, Module, PredeclaredType, ScalarKind,
+    TypeInner, VectorSize,
+};
+
+use crate::interpreter::{
+    value::{scalar_shape, shaped_type, Scalar, Value},
+    Interpreter,
+};
+
+use super::bits;
+
+/// The components of a scalar or vector argument to a math function. Floats
+/// are widened to `f64` and rounded back to the argument's width afterwards.
+#[derive(Clone, Debug)]
+enum Components {
+    Float(Vec<f64>),
+    Sint(Vec<i32>),
+    Uint(Vec<u32>),
+}
+
+impl Components {
+    fn read(value: &Value) -> anyhow::Result<Self> {
+        let invalid = || anyhow::anyhow!("Invalid math function argument: {:?}", value);
+        let components = value.try_components().map_err(|_| invalid())?;
+        let components = match components[0] {
+            Scalar::F32(_) | Scalar::F64(_) => Self::Float(
+                components
+                    .iter()
+                    .map(|&x| match x {
+                        Scalar::F32(x) => Ok(x as f64),
+                        Scalar::F64(x) => Ok(x),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            Scalar::I32(_) => Self::Sint(
+                components
+                    .iter()
+                    .map(|&x| match x {
+                        Scalar::I32(x) => Ok(x),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            Scalar::U32(_) => Self::Uint(
+                components
+                    .iter()
+                    .map(|&x| match x {
+                        Scalar::U32(x) => Ok(x),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            Scalar::Bool(_) => return Err(invalid()),
+        };
+        Ok(components)
+    }
+
+    fn floats(&self) -> anyhow::Result<&[f64]> {
+        match self {
+            Self::Float(values) => Ok(values),
+            _ => Err(anyhow::anyhow!("Expected a float argument, got {:?}", self)),
+        }
+    }
+
+    fn sints(&self) -> anyhow::Result<&[i32]> {
+        match self {
+            Self::Sint(values) => Ok(values),
+            _ => Err(anyhow::anyhow!("Expected an i32 argument, got {:?}", self)),
+        }
+    }
+
+    fn uints(&self) -> anyhow::Result<&[u32]> {
+        match self {
+            Self::Uint(values) => Ok(values),
+            _ => Err(anyhow::anyhow!("Expected a u32 argument, got {:?}", self)),
+        }
+    }
+
+    /// Builds a scalar or vector with the components' kind and the width of
+    /// `ty`.
+    fn write(&self, ty: &TypeInner) -> anyhow::Result<Value> {
+        let (_, _, width) =
+            scalar_shape(ty).ok_or(anyhow::anyhow!("Invalid math result type: {:?}", ty))?;
+        let components = match (self, width) {
+            (Self::Float(values), 4) => values.iter().map(|&x| Scalar::F32(x as f32)).collect(),
+            (Self::Float(values), _) => values.iter().map(|&x| Scalar::F64(x)).collect(),
+            (Self::Sint(values), _) => values.iter().map(|&x| Scalar::I32(x)).collect(),
+            (Self::Uint(values), _) => values.iter().map(|&x| Scalar::U32(x)).collect(),
+        };
+        Value::from_components(components)
+    }
+}
+
+/// Applies `f` to the `i`th component of every argument in turn, broadcasting
+/// scalar arguments.
+fn broadcast<T: Copy>(args: &[&[T]], count: usize, f: impl Fn(&[T]) -> T) -> Vec<T> {
+    (0..count)
+        .map(|i| {
+            let components = args
+                .iter()
+                .map(|arg| if arg.len() == 1 { arg[0] } else { arg[i] })
+                .collect::<Vec<_>>();
+            f(&components)
+        })
+        .collect()
+}
+
+type ComponentFn<'f, T> = Option<&'f dyn Fn(&[T]) -> T>;
+
+/// Evaluates a componentwise function. The result has the type of the first
+/// vector argument, or of the first argument if they are all scalars.
+fn componentwise(
+    module: &Module,
+    fun: MathFunction,
+    args: &[Value],
+    float: ComponentFn<f64>,
+    sint: ComponentFn<i32>,
+    uint: ComponentFn<u32>,
+) -> anyhow::Result<Value> {
+    let ty = args
+        .iter()
+        .map(|arg| arg.ty(module))
+        .find(|ty| matches!(ty, TypeInner::Vector { .. }))
+        .unwrap_or(args[0].ty(module));
+    let count = match ty {
+        TypeInner::Vector { size, .. } => size as usize,
+        _ => 1,
+    };
+    let components = args
+        .iter()
+        .map(Components::read)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let unsupported = || {
+        anyhow::anyhow!(
+            "Unsupported argument for {:?}: {:?}",
+            fun,
+            args[0].ty(module)
+        )
+    };
+
+    let result = match components[0] {
+        Components::Float(_) => {
+            let float = float.ok_or_else(unsupported)?;
+            let args = components
+                .iter()
+                .map(Components::floats)
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Components::Float(broadcast(&args, count, float))
+        }
+        Components::Sint(_) => {
+            let sint = sint.ok_or_else(unsupported)?;
+            let args = components
+                .iter()
+                .map(Components::sints)
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Components::Sint(broadcast(&args, count, sint))
+        }
+        Components::Uint(_) => {
+            let uint = uint.ok_or_else(unsupported)?;
+            let args = components
+                .iter()
+                .map(Components::uints)
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Components::Uint(broadcast(&args, count, uint))
+        }
+    };
+    result.write(&ty)
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn length(v: &[f64]) -> f64 {
+    dot(v, v).sqrt()
+}
+
+fn sign(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+/// Splits `x` into a fraction in `[0.5, 1)` and a power of two.
+fn frexp(x: f64) -> (f64, i32) {
+    if x == 0.0 || !x.is_finite() {
+        return (x, 0);
+    }
+    let mut exp = x.abs().log2().floor() as i32 + 1;
+    let mut fract = x / 2f64.powi(exp);
+    if fract.abs() >= 1.0 {
+        fract /= 2.0;
+        exp += 1;
+    } else if fract.abs() < 0.5 {
+        fract *= 2.0;
+        exp -= 1;
+    }
+    (fract, exp)
+}
+
+/// Reads a matrix into a list of `f64` columns.
+fn read_columns(value: &Value) -> anyhow::Result<Vec<Vec<f64>>> {
+    let Value::Matrix(columns) = value else {
+        return Err(anyhow::anyhow!("Expected a matrix, got {:?}", value));
+    };
+    columns
+        .iter()
+        .map(|column| {
+            Ok(Components::read(&Value::Vector(column.clone()))?
+                .floats()?
+                .to_vec())
+        })
+        .collect()
+}
+
+/// Builds a matrix from a list of `f64` columns, with floats of `width`.
+fn write_columns(width: Bytes, columns: &[Vec<f64>]) -> Value {
+    Value::Matrix(
+        columns
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .map(|&x| match width {
+                        4 => Scalar::F32(x as f32),
+                        _ => Scalar::F64(x),
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+/// Removes row `row` and column `column` from a square matrix.
+fn minor(m: &[Vec<f64>], row: usize, column: usize) -> Vec<Vec<f64>> {
+    m.iter()
+        .enumerate()
+        .filter(|&(j, _)| j != column)
+        .map(|(_, c)| {
+            c.iter()
+                .enumerate()
+                .filter(|&(i, _)| i != row)
+                .map(|(_, &x)| x)
+                .collect()
+        })
+        .collect()
+}
+
+/// Determinant of a square matrix, by cofactor expansion along the first
+/// column.
+fn determinant(m: &[Vec<f64>]) -> f64 {
+    match m.len() {
+        1 => m[0][0],
+        2 => m[0][0] * m[1][1] - m[1][0] * m[0][1],
+        n => (0..n)
+            .map(|row| {
+                let sign = if row % 2 == 0 { 1.0 } else { -1.0 };
+                sign * m[0][row] * determinant(&minor(m, row, 0))
+            })
+            .sum(),
+    }
+}
+
+/// Inverse of a square matrix: its transposed cofactor matrix divided by its
+/// determinant.
+fn inverse(m: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let det = determinant(m);
+    let n = m.len();
+    (0..n)
+        .map(|column| {
+            (0..n)
+                .map(|row| {
+                    let sign = if (row + column) % 2 == 0 { 1.0 } else { -1.0 };
+                    sign * determinant(&minor(m, column, row)) / det
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl Interpreter {
+    pub(super) fn math(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        fun: MathFunction,
+        args: &[Handle<Expression>],
+    ) -> anyhow::Result<Value> {
+        let args = args
+            .iter()
+            .map(|arg| self.expression(module, *arg, func))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let expected = match fun {
+            MathFunction::Abs
+            | MathFunction::Saturate
+            | MathFunction::Cos
+            | MathFunction::Cosh
+            | MathFunction::Sin
+            | MathFunction::Sinh
+            | MathFunction::Tan
+            | MathFunction::Tanh
+            | MathFunction::Acos
+            | MathFunction::Asin
+            | MathFunction::Atan
+            | MathFunction::Asinh
+            | MathFunction::Acosh
+            | MathFunction::Atanh
+            | MathFunction::Radians
+            | MathFunction::Degrees
+            | MathFunction::Ceil
+            | MathFunction::Floor
+            | MathFunction::Round
+            | MathFunction::Fract
+            | MathFunction::Trunc
+            | MathFunction::Modf
+            | MathFunction::Frexp
+            | MathFunction::Exp
+            | MathFunction::Exp2
+            | MathFunction::Log
+            | MathFunction::Log2
+            | MathFunction::Length
+            | MathFunction::Normalize
+            | MathFunction::Sign
+            | MathFunction::Sqrt
+            | MathFunction::InverseSqrt
+            | MathFunction::Inverse
+            | MathFunction::Transpose
+            | MathFunction::Determinant
+            | MathFunction::CountTrailingZeros
+            | MathFunction::CountLeadingZeros
+            | MathFunction::CountOneBits
+            | MathFunction::ReverseBits
+            | MathFunction::FindLsb
+            | MathFunction::FindMsb
+            | MathFunction::Pack4x8snorm
+            | MathFunction::Pack4x8unorm
+            | MathFunction::Pack2x16snorm
+            | MathFunction::Pack2x16unorm
+            | MathFunction::Pack2x16float
+            | MathFunction::Unpack4x8snorm
+            | MathFunction::Unpack4x8unorm
+            | MathFunction::Unpack2x16snorm
+            | MathFunction::Unpack2x16unorm
+            | MathFunction::Unpack2x16float => 1,
+            MathFunction::Min
+            | MathFunction::Max
+            | MathFunction::Atan2
+            | MathFunction::Ldexp
+            | MathFunction::Pow
+            | MathFunction::Dot
+            | MathFunction::Outer
+            | MathFunction::Cross
+            | MathFunction::Distance
+            | MathFunction::Reflect
+            | MathFunction::Step => 2,
+            MathFunction::Clamp
+            | MathFunction::FaceForward
+            | MathFunction::Refract
+            | MathFunction::Fma
+            | MathFunction::Mix
+            | MathFunction::SmoothStep
+            | MathFunction::ExtractBits => 3,
+            MathFunction::InsertBits => 4,
+        };
+        if args.len() != expected {
+            return Err(anyhow::anyhow!(
+                "Invalid call to {:?}: expected {} arguments, got {}",
+                fun,
+                expected,
+                args.len()
+            ));
+        }
+
+        let float1 =
+            |f: fn(f64) -> f64| componentwise(module, fun, &args, Some(&|x| f(x[0])), None, None);
+        let float2 = |f: fn(f64, f64) -> f64| {
+            componentwise(module, fun, &args, Some(&|x| f(x[0], x[1])), None, None)
+        };
+
+        match fun {
+            MathFunction::Abs => componentwise(
+                module,
+                fun,
+                &args,
+                Some(&|x| x[0].abs()),
+                Some(&|x| x[0].wrapping_abs()),
+                Some(&|x| x[0]),
+            ),
+            MathFunction::Min => componentwise(
+                module,
+                fun,
+                &args,
+                Some(&|x| x[0].min(x[1])),
+                Some(&|x| x[0].min(x[1])),
+                Some(&|x| x[0].min(x[1])),
+            ),
+            MathFunction::Max => componentwise(
+                module,
+                fun,
+                &args,
+                Some(&|x| x[0].max(x[1])),
+                Some(&|x| x[0].max(x[1])),
+                Some(&|x| x[0].max(x[1])),
+            ),
+            MathFunction::Clamp => componentwise(
+                module,
+                fun,
+                &args,
+                Some(&|x| x[0].max(x[1]).min(x[2])),
+                Some(&|x| x[0].max(x[1]).min(x[2])),
+                Some(&|x| x[0].max(x[1]).min(x[2])),
+            ),
+            MathFunction::Saturate => float1(|x| x.clamp(0.0, 1.0)),
+            MathFunction::Cos => float1(f64::cos),
+            MathFunction::Cosh => float1(f64::cosh),
+            MathFunction::Sin => float1(f64::sin),
+            MathFunction::Sinh => float1(f64::sinh),
+            MathFunction::Tan => float1(f64::tan),
+            MathFunction::Tanh => float1(f64::tanh),
+            MathFunction::Acos => float1(f64::acos),
+            MathFunction::Asin => float1(f64::asin),
+            MathFunction::Atan => float1(f64::atan),
+            MathFunction::Atan2 => float2(f64::atan2),
+            MathFunction::Asinh => float1(f64::asinh),
+            MathFunction::Acosh => float1(f64::acosh),
+            MathFunction::Atanh => float1(f64::atanh),
+            MathFunction::Radians => float1(f64::to_radians),
+            MathFunction::Degrees => float1(f64::to_degrees),
+            MathFunction::Ceil => float1(f64::ceil),
+            MathFunction::Floor => float1(f64::floor),
+            MathFunction::Round => float1(f64::round_ties_even),
+            MathFunction::Fract => float1(|x| x - x.floor()),
+            MathFunction::Trunc => float1(f64::trunc),
+            MathFunction::Exp => float1(f64::exp),
+            MathFunction::Exp2 => float1(f64::exp2),
+            MathFunction::Log => float1(f64::ln),
+            MathFunction::Log2 => float1(f64::log2),
+            MathFunction::Pow => float2(f64::powf),
+            MathFunction::Sqrt => float1(f64::sqrt),
+            MathFunction::InverseSqrt => float1(|x| 1.0 / x.sqrt()),
+            MathFunction::Step => float2(|edge, x| if x >= edge { 1.0 } else { 0.0 }),
+            MathFunction::Sign => componentwise(
+                module,
+                fun,
+                &args,
+                Some(&|x| sign(x[0])),
+                Some(&|x| x[0].signum()),
+                None,
+            ),
+            MathFunction::Fma => componentwise(
+                module,
+                fun,
+                &args,
+                Some(&|x| x[0].mul_add(x[1], x[2])),
+                None,
+                None,
+            ),
+            MathFunction::Mix => componentwise(
+                module,
+                fun,
+                &args,
+                Some(&|x| x[0] * (1.0 - x[2]) + x[1] * x[2]),
+                None,
+                None,
+            ),
+            MathFunction::SmoothStep => componentwise(
+                module,
+                fun,
+                &args,
+                Some(&|x| {
+                    let t = ((x[2] - x[0]) / (x[1] - x[0])).clamp(0.0, 1.0);
+                    t * t * (3.0 - 2.0 * t)
+                }),
+                None,
+                None,
+            ),
+            MathFunction::Ldexp => {
+                let fract = Components::read(&args[0])?;
+                let exp = Components::read(&args[1])?;
+                let result = fract
+                    .floats()?
+                    .iter()
+                    .zip(exp.sints()?.iter().cycle())
+                    .map(|(x, &e)| x * 2f64.powi(e))
+                    .collect();
+                Components::Float(result).write(&args[0].ty(module))
+            }
+            MathFunction::Modf | MathFunction::Frexp => {
+                let (size, _, width) = scalar_shape(&args[0].ty(module)).ok_or(anyhow::anyhow!(
+                    "Invalid argument for {:?}: {:?}",
+                    fun,
+                    args[0].ty(module)
+                ))?;
+                let predeclared = match fun {
+                    MathFunction::Modf => PredeclaredType::ModfResult { size, width },
+                    _ => PredeclaredType::FrexpResult { size, width },
+                };
+                let handle = module
+                    .special_types
+                    .predeclared_types
+                    .get(&predeclared)
+                    .ok_or(anyhow::anyhow!("Missing result type for {:?}", fun))?;
+                let TypeInner::Struct { ref members, .. } = module.types[*handle].inner else {
+                    return Err(anyhow::anyhow!("Invalid result type for {:?}", fun));
+                };
+
+                let x = Components::read(&args[0])?;
+                let x = x.floats()?;
+                let (fract, second) = match fun {
+                    MathFunction::Modf => (
+                        Components::Float(x.iter().map(|x| x - x.trunc()).collect()),
+                        Components::Float(x.iter().map(|x| x.trunc()).collect()),
+                    ),
+                    _ => {
+                        let (fract, exp) = x.iter().map(|&x| frexp(x)).unzip();
+                        (Components::Float(fract), Components::Sint(exp))
+                    }
+                };
+
+                let components = members
+                    .iter()
+                    .zip([fract, second])
+                    .map(|(member, components)| components.write(&module.types[member.ty].inner))
+                    .collect::<anyhow::Result<_>>()?;
+                Ok(Value::Composite {
+                    ty: *handle,
+                    components,
+                })
+            }
+            MathFunction::Dot => {
+                let (_, kind, width) = scalar_shape(&args[0].ty(module)).ok_or(anyhow::anyhow!(
+                    "Invalid argument for {:?}: {:?}",
+                    fun,
+                    args[0].ty(module)
+                ))?;
+                let ty = shaped_type(None, kind, width);
+                let result = match (Components::read(&args[0])?, Components::read(&args[1])?) {
+                    (Components::Float(a), Components::Float(b)) => {
+                        Components::Float(vec![dot(&a, &b)])
+                    }
+                    (Components::Sint(a), Components::Sint(b)) => Components::Sint(vec![a
+                        .iter()
+                        .zip(&b)
+                        .fold(0i32, |sum, (a, b)| sum.wrapping_add(a.wrapping_mul(*b)))]),
+                    (Components::Uint(a), Components::Uint(b)) => Components::Uint(vec![a
+                        .iter()
+                        .zip(&b)
+                        .fold(0u32, |sum, (a, b)| sum.wrapping_add(a.wrapping_mul(*b)))]),
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "Invalid arguments for {:?}: {:?}, {:?}",
+                            fun,
+                            args[0].ty(module),
+                            args[1].ty(module)
+                        ))
+                    }
+                };
+                result.write(&ty)
+            }
+            MathFunction::Cross => {
+                let a = Components::read(&args[0])?;
+                let b = Components::read(&args[1])?;
+                let (a, b) = (a.floats()?, b.floats()?);
+                if a.len() != 3 || b.len() != 3 {
+                    return Err(anyhow::anyhow!("Invalid arguments for {:?}", fun));
+                }
+                Components::Float(vec![
+                    a[1] * b[2] - a[2] * b[1],
+                    a[2] * b[0] - a[0] * b[2],
+                    a[0] * b[1] - a[1] * b[0],
+                ])
+                .write(&args[0].ty(module))
+            }
+            MathFunction::Length | MathFunction::Distance => {
+                let (_, kind, width) = scalar_shape(&args[0].ty(module)).ok_or(anyhow::anyhow!(
+                    "Invalid argument for {:?}: {:?}",
+                    fun,
+                    args[0].ty(module)
+                ))?;
+                let a = Components::read(&args[0])?;
+                let mut v = a.floats()?.to_vec();
+                if fun == MathFunction::Distance {
+                    let b = Components::read(&args[1])?;
+                    for (a, b) in v.iter_mut().zip(b.floats()?) {
+                        *a -= b;
+                    }
+                }
+                Components::Float(vec![length(&v)]).write(&shaped_type(None, kind, width))
+            }
+            MathFunction::Normalize => {
+                let v = Components::read(&args[0])?;
+                let v = v.floats()?;
+                let length = length(v);
+                Components::Float(v.iter().map(|x| x / length).collect()).write(&args[0].ty(module))
+            }
+            MathFunction::FaceForward => {
+                let [n, i, reference] = [&args[0], &args[1], &args[2]].map(Components::read);
+                let (n, i, reference) = (n?, i?, reference?);
+                let n = n.floats()?;
+                let flip = dot(reference.floats()?, i.floats()?) >= 0.0;
+                Components::Float(n.iter().map(|&x| if flip { -x } else { x }).collect())
+                    .write(&args[0].ty(module))
+            }
+            MathFunction::Reflect => {
+                let i = Components::read(&args[0])?;
+                let n = Components::read(&args[1])?;
+                let (i, n) = (i.floats()?, n.floats()?);
+                let d = 2.0 * dot(n, i);
+                Components::Float(i.iter().zip(n).map(|(i, n)| i - d * n).collect())
+                    .write(&args[0].ty(module))
+            }
+            MathFunction::Refract => {
+                let i = Components::read(&args[0])?;
+                let n = Components::read(&args[1])?;
+                let eta = Components::read(&args[2])?;
+                let (i, n, eta) = (i.floats()?, n.floats()?, eta.floats()?[0]);
+                let d = dot(n, i);
+                let k = 1.0 - eta * eta * (1.0 - d * d);
+                let result = if k < 0.0 {
+                    vec![0.0; i.len()]
+                } else {
+                    i.iter()
+                        .zip(n)
+                        .map(|(i, n)| eta * i - (eta * d + k.sqrt()) * n)
+                        .collect()
+                };
+                Components::Float(result).write(&args[0].ty(module))
+            }
+            MathFunction::Outer => {
+                let a = Components::read(&args[0])?;
+                let b = Components::read(&args[1])?;
+                let TypeInner::Vector { width, .. } = args[0].ty(module) else {
+                    return Err(anyhow::anyhow!("Invalid arguments for {:?}", fun));
+                };
+                let (a, b) = (a.floats()?, b.floats()?);
+                let result = b
+                    .iter()
+                    .map(|b| a.iter().map(|a| a * b).collect())
+                    .collect::<Vec<_>>();
+                Ok(write_columns(width, &result))
+            }
+            MathFunction::Transpose => {
+                let TypeInner::Matrix { rows, width, .. } = args[0].ty(module) else {
+                    return Err(anyhow::anyhow!(
+                        "Expected a matrix, got {:?}",
+                        args[0].ty(module)
+                    ));
+                };
+                let m = read_columns(&args[0])?;
+                let result = (0..rows as usize)
+                    .map(|row| m.iter().map(|column| column[row]).collect())
+                    .collect::<Vec<_>>();
+                Ok(write_columns(width, &result))
+            }
+            MathFunction::Determinant | MathFunction::Inverse => {
+                let TypeInner::Matrix {
+                    columns,
+                    rows,
+                    width,
+                } = args[0].ty(module)
+                else {
+                    return Err(anyhow::anyhow!(
+                        "Expected a matrix, got {:?}",
+                        args[0].ty(module)
+                    ));
+                };
+                if columns != rows {
+                    return Err(anyhow::anyhow!(
+                        "Invalid argument for {:?}: matrix is not square",
+                        fun
+                    ));
+                }
+                let m = read_columns(&args[0])?;
+                if fun == MathFunction::Determinant {
+                    let ty = shaped_type(None, ScalarKind::Float, width);
+                    return Components::Float(vec![determinant(&m)]).write(&ty);
+                }
+                Ok(write_columns(width, &inverse(&m)))
+            }
+            MathFunction::CountTrailingZeros => componentwise(
+                module,
+                fun,
+                &args,
+                None,
+                Some(&|x| x[0].trailing_zeros() as i32),
+                Some(&|x| x[0].trailing_zeros()),
+            ),
+            MathFunction::CountLeadingZeros => componentwise(
+                module,
+                fun,
+                &args,
+                None,
+                Some(&|x| x[0].leading_zeros() as i32),
+                Some(&|x| x[0].leading_zeros()),
+            ),
+            MathFunction::CountOneBits => componentwise(
+                module,
+                fun,
+                &args,
+                None,
+                Some(&|x| x[0].count_ones() as i32),
+                Some(&|x| x[0].count_ones()),
+            ),
+            MathFunction::ReverseBits => componentwise(
+                module,
+                fun,
+                &args,
+                None,
+                Some(&|x| x[0].reverse_bits()),
+                Some(&|x| x[0].reverse_bits()),
+            ),
+            MathFunction::FindLsb => componentwise(
+                module,
+                fun,
+                &args,
+                None,
+                Some(&|x| bits::first_trailing_bit(x[0] as u32) as i32),
+                Some(&|x| bits::first_trailing_bit(x[0])),
+            ),
+            MathFunction::FindMsb => componentwise(
+                module,
+                fun,
+                &args,
+                None,
+                Some(&|x| bits::first_leading_bit_i32(x[0])),
+                Some(&|x| bits::first_leading_bit_u32(x[0])),
+            ),
+            MathFunction::ExtractBits | MathFunction::InsertBits => {
+                // the offset and count are scalar u32s, whatever the type of the bits
+                let (bits_args, range) = args.split_at(args.len() - 2);
+                let range = range
+                    .iter()
+                    .map(Value::try_u32)
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                let (offset, count) = (range[0], range[1]);
+                match fun {
+                    MathFunction::ExtractBits => componentwise(
+                        module,
+                        fun,
+                        bits_args,
+                        None,
+                        Some(&|x| bits::extract_bits_i32(x[0], offset, count)),
+                        Some(&|x| bits::extract_bits_u32(x[0], offset, count)),
+                    ),
+                    _ => componentwise(
+                        module,
+                        fun,
+                        bits_args,
+                        None,
+                        Some(&|x| {
+                            bits::insert_bits(x[0] as u32, x[1] as u32, offset, count) as i32
+                        }),
+                        Some(&|x| bits::insert_bits(x[0], x[1], offset, count)),
+                    ),
+                }
+            }
+            MathFunction::Pack4x8snorm
+            | MathFunction::Pack4x8unorm
+            | MathFunction::Pack2x16snorm
+            | MathFunction::Pack2x16unorm
+            | MathFunction::Pack2x16float => {
+                let v = Components::read(&args[0])?;
+                let v = v.floats()?;
+                let packed = match fun {
+                    MathFunction::Pack4x8snorm => bits::pack4x8snorm(v),
+                    MathFunction::Pack4x8unorm => bits::pack4x8unorm(v),
+                    MathFunction::Pack2x16snorm => bits::pack2x16snorm(v),
+                    MathFunction::Pack2x16unorm => bits::pack2x16unorm(v),
+                    _ => bits::pack2x16float(v),
+                };
+                Components::Uint(vec![packed]).write(&shaped_type(None, ScalarKind::Uint, 4))
+            }
+            MathFunction::Unpack4x8snorm
+            | MathFunction::Unpack4x8unorm
+            | MathFunction::Unpack2x16snorm
+            | MathFunction::Unpack2x16unorm
+            | MathFunction::Unpack2x16float => {
+                let e = args[0].try_u32()?;
+                let (size, unpacked) = match fun {
+                    MathFunction::Unpack4x8snorm => (VectorSize::Quad, bits::unpack4x8snorm(e)),
+                    MathFunction::Unpack4x8unorm => (VectorSize::Quad, bits::unpack4x8unorm(e)),
+                    MathFunction::Unpack2x16snorm => (VectorSize::Bi, bits::unpack2x16snorm(e)),
+                    MathFunction::Unpack2x16unorm => (VectorSize::Bi, bits::unpack2x16unorm(e)),
+                    _ => (VectorSize::Bi, bits::unpack2x16float(e)),
+                };
+                Components::Float(unpacked).write(&shaped_type(Some(size), ScalarKind::Float, 4))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{determinant, inverse};
+
+    /// Product of two square matrices given as columns.
+    fn multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        b.iter()
+            .map(|column| {
+                (0..a.len())
+                    .map(|row| (0..a.len()).map(|k| a[k][row] * column[k]).sum())
+                    .collect()
+            })
+            .collect()
+    }
+
+    // `inverse` has no WGSL builtin, so it is only reachable from other
+    // front ends
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let matrices = [
+            vec![vec![4.0, 2.0], vec![7.0, 6.0]],
+            vec![
+                vec![2.0, 0.0, 1.0],
+                vec![1.0, 3.0, 2.0],
+                vec![1.0, 1.0, 4.0],
+            ],
+            vec![
+                vec![2.0, 1.0, 0.0, 0.0],
+                vec![1.0, 3.0, 1.0, 0.0],
+                vec![0.0, 1.0, 4.0, 1.0],
+                vec![3.0, 0.0, 1.0, 5.0],
+            ],
+        ];
+        for m in matrices {
+            assert_ne!(determinant(&m), 0.0);
+            for (i, column) in multiply(&inverse(&m), &m).iter().enumerate() {
+                for (j, &x) in column.iter().enumerate() {
+                    let expected = if i == j { 1.0 } else { 0.0 };
+                    assert!((x - expected).abs() < 1e-12, "{:?}: {:?}", m, x);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_of_2x2() {
+        let m = vec![vec![4.0, 2.0], vec![7.0, 6.0]];
+        assert_eq!(inverse(&m), vec![vec![0.6, -0.2], vec![-0.7, 0.4]]);
+    }
+}
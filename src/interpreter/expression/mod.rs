@@ -1,91 +1,198 @@
 use naga::{Expression, Handle, Module, TypeInner};
 
-use super::{type_name, Interpreter, Value};
+use super::{value::Scalar, Interpreter, Value};
 
+pub mod access;
 pub mod binary;
+pub mod bits;
+pub mod cast;
+pub mod constant;
+pub mod math;
+pub mod pointer;
+pub mod relational;
+pub mod select;
+pub mod unary;
 
-impl<'a> Interpreter<'a> {
+impl Interpreter {
+    /// Returns the value of `expr`, reusing the result cached in the current
+    /// frame if it has already been evaluated.
     pub(super) fn expression(
         &mut self,
-        module: &'a Module,
+        module: &Module,
         expr: Handle<Expression>,
         func: &naga::Function,
-    ) -> anyhow::Result<Value<'a>> {
+    ) -> anyhow::Result<Value> {
+        if let Some(value) = self
+            .current_frame()
+            .and_then(|frame| frame.expressions.get(&expr))
+        {
+            return Ok(value.to_owned());
+        }
+        self.emit(module, expr, func)
+    }
+
+    /// Evaluates `expr` and caches the result in the current frame, replacing
+    /// any value from a previous evaluation.
+    pub(super) fn emit(
+        &mut self,
+        module: &Module,
+        expr: Handle<Expression>,
+        func: &naga::Function,
+    ) -> anyhow::Result<Value> {
+        let value = self.evaluate(module, expr, func)?;
+        self.current_frame_mut()
+            .ok_or(anyhow::anyhow!("No frame found"))?
+            .expressions
+            .insert(expr, value.to_owned());
+        Ok(value)
+    }
+
+    fn evaluate(
+        &mut self,
+        module: &Module,
+        expr: Handle<Expression>,
+        func: &naga::Function,
+    ) -> anyhow::Result<Value> {
         if self.verbosity > 1 {
             println!("Expression: {:?}", expr);
         }
-        let expr = func.expressions[expr].to_owned();
-        match expr {
-            naga::Expression::Literal(lit) => match lit {
-                naga::Literal::Bool(val) => Ok(Value::from_pod(
-                    &TypeInner::Scalar {
-                        kind: naga::ScalarKind::Bool,
-                        width: 1,
-                    },
-                    val as u8,
-                )),
-                naga::Literal::I32(val) => Ok(Value::from_pod(
-                    &TypeInner::Scalar {
-                        kind: naga::ScalarKind::Sint,
-                        width: 4,
-                    },
-                    val,
-                )),
-                naga::Literal::U32(val) => Ok(Value::from_pod(
-                    &TypeInner::Scalar {
-                        kind: naga::ScalarKind::Uint,
-                        width: 4,
-                    },
-                    val,
-                )),
-                naga::Literal::F32(val) => Ok(Value::from_pod(
-                    &TypeInner::Scalar {
-                        kind: naga::ScalarKind::Float,
-                        width: 4,
-                    },
-                    val,
-                )),
-                naga::Literal::F64(val) => Ok(Value::from_pod(
-                    &TypeInner::Scalar {
-                        kind: naga::ScalarKind::Float,
-                        width: 8,
-                    },
-                    val,
-                )),
-            },
+        match func.expressions[expr].to_owned() {
+            naga::Expression::Literal(lit) => Ok(literal(lit)),
             naga::Expression::FunctionArgument(arg) => {
-                let arg = &func.arguments[arg as usize];
-                let name = arg
-                    .name
-                    .as_ref()
-                    .ok_or(anyhow::anyhow!("Argument has no name"))?;
                 let value = self
-                    .current_scope()
-                    .ok_or(anyhow::anyhow!("No scope found"))?
-                    .variables
-                    .get(name)
-                    .ok_or(anyhow::anyhow!("Variable not found: {}", name))?
+                    .current_frame()
+                    .ok_or(anyhow::anyhow!("No frame found"))?
+                    .arguments
+                    .get(arg as usize)
+                    .ok_or(anyhow::anyhow!("Argument not found: {}", arg))?
                     .to_owned();
                 Ok(value)
             }
+            naga::Expression::LocalVariable(local) => self.local_pointer(func, local),
+            naga::Expression::GlobalVariable(global) => Ok(self.global_pointer(module, global)),
             naga::Expression::Compose { ty, components } => {
-                let ty = module.types.get_handle(ty).unwrap();
-                let ty_name = type_name(ty).unwrap();
-                let size = ty.inner.size(module.to_ctx());
-                let mut data = vec![0; size as usize];
-                for (i, component) in components.iter().enumerate() {
-                    let component = self.expression(module, *component, func)?;
-                    let component_size = component.data.len();
-                    data[i * component_size..(i + 1) * component_size]
-                        .copy_from_slice(&component.data);
-                }
-                let value = Value::from_data(&ty.inner, data);
-                Ok(value)
+                let components = components
+                    .iter()
+                    .map(|component| self.expression(module, *component, func))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                compose(module, ty, components)
+            }
+            naga::Expression::Constant(constant) => {
+                self.constants
+                    .get(&constant)
+                    .cloned()
+                    .ok_or(anyhow::anyhow!(
+                        "Constant {:?} has not been loaded",
+                        constant
+                    ))
             }
+            naga::Expression::ZeroValue(ty) => Value::zeroed(module, ty),
             naga::Expression::Binary { op, left, right } => {
                 self.binary(module, func, op, left, right)
             }
+            naga::Expression::Unary { op, expr } => self.unary(module, func, op, expr),
+            naga::Expression::Select {
+                condition,
+                accept,
+                reject,
+            } => self.select(module, func, condition, accept, reject),
+            naga::Expression::As {
+                expr,
+                kind,
+                convert,
+            } => self.cast(module, func, expr, kind, convert),
+            naga::Expression::Load { pointer } => {
+                let pointer = self.pointer(module, pointer, func)?;
+                self.load(module, &pointer)
+            }
+            naga::Expression::AccessIndex { base, index } => {
+                self.access_index(module, func, base, index)
+            }
+            naga::Expression::Access { base, index } => {
+                let index = self.index(module, func, index)?;
+                self.access_index(module, func, base, index)
+            }
+            naga::Expression::Splat { size, value } => {
+                self.expression(module, value, func)?.try_splat(size)
+            }
+            naga::Expression::Swizzle {
+                size,
+                vector,
+                pattern,
+            } => self.swizzle(module, func, size, vector, pattern),
+            naga::Expression::Math {
+                fun,
+                arg,
+                arg1,
+                arg2,
+                arg3,
+            } => {
+                let args = [Some(arg), arg1, arg2, arg3]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                self.math(module, func, fun, &args)
+            }
+            naga::Expression::Relational { fun, argument } => {
+                self.relational(module, func, fun, argument)
+            }
+            naga::Expression::ArrayLength(array) => {
+                let pointer = self.pointer(module, array, func)?;
+                self.array_length(module, &pointer)
+            }
+            naga::Expression::CallResult(function) => {
+                self.deferred_call(module, func, function, expr)
+            }
+            naga::Expression::AtomicResult { .. } => Err(anyhow::anyhow!(
+                "Result of atomic operation {:?} used before the operation",
+                expr
+            )),
             expr => todo!("{:?}", expr),
         }
     }
 }
+
+fn literal(lit: naga::Literal) -> Value {
+    let scalar = match lit {
+        naga::Literal::Bool(val) => Scalar::Bool(val),
+        naga::Literal::I32(val) => Scalar::I32(val),
+        naga::Literal::U32(val) => Scalar::U32(val),
+        naga::Literal::F32(val) => Scalar::F32(val),
+        naga::Literal::F64(val) => Scalar::F64(val),
+    };
+    Value::Scalar(scalar)
+}
+
+/// Builds a value of type `ty` from its components. Vectors and matrices may
+/// be composed from smaller vectors, which are flattened.
+fn compose(
+    module: &Module,
+    ty: Handle<naga::Type>,
+    components: Vec<Value>,
+) -> anyhow::Result<Value> {
+    let flatten = |components: Vec<Value>| {
+        components
+            .iter()
+            .map(|component| component.try_components().map(|c| c.to_vec()))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(|components| components.concat())
+    };
+    let value = match module.types[ty].inner {
+        TypeInner::Vector { .. } => Value::Vector(flatten(components)?),
+        TypeInner::Matrix { rows, .. } => Value::Matrix(
+            flatten(components)?
+                .chunks(rows as usize)
+                .map(|column| column.to_vec())
+                .collect(),
+        ),
+        TypeInner::Struct { .. } | TypeInner::Array { .. } => Value::Composite { ty, components },
+        ref ty => return Err(anyhow::anyhow!("Invalid composite type: {:?}", ty)),
+    };
+    if value.ty(module) != module.types[ty].inner {
+        return Err(anyhow::anyhow!(
+            "Invalid components for {:?}",
+            module.types[ty].inner
+        ));
+    }
+    Ok(value)
+}
@@ -0,0 +1,126 @@
+use naga::{AddressSpace, Expression, Function, Handle, Module};
+
+use crate::interpreter::{
+    value::{Pointer, PointerTarget, Scalar, Value},
+    Interpreter,
+};
+
+impl Interpreter {
+    /// Evaluates `expr`, which must produce a pointer.
+    pub(in crate::interpreter) fn pointer(
+        &mut self,
+        module: &Module,
+        expr: Handle<Expression>,
+        func: &Function,
+    ) -> anyhow::Result<Pointer> {
+        match self.expression(module, expr, func)? {
+            Value::Pointer(pointer) => Ok(pointer),
+            value => Err(anyhow::anyhow!(
+                "Invalid pointer expression: {:?} evaluates to {:?}",
+                func.expressions[expr],
+                value.ty(module)
+            )),
+        }
+    }
+
+    /// A pointer to a local variable of the current frame.
+    pub(super) fn local_pointer(
+        &self,
+        func: &Function,
+        local: Handle<naga::LocalVariable>,
+    ) -> anyhow::Result<Value> {
+        let frame = self
+            .frames
+            .len()
+            .checked_sub(1)
+            .ok_or(anyhow::anyhow!("No frame found"))?;
+        Ok(Value::Pointer(Pointer {
+            target: PointerTarget::Local { frame, local },
+            ty: func.local_variables[local].ty,
+            path: vec![],
+            space: AddressSpace::Function,
+        }))
+    }
+
+    /// A pointer to a global variable, in the variable's address space.
+    pub(super) fn global_pointer(
+        &self,
+        module: &Module,
+        global: Handle<naga::GlobalVariable>,
+    ) -> Value {
+        let variable = &module.global_variables[global];
+        Value::Pointer(Pointer {
+            target: PointerTarget::Global(global),
+            ty: variable.ty,
+            path: vec![],
+            space: variable.space,
+        })
+    }
+
+    /// The storage of the variable `target` refers to.
+    fn variable(&self, module: &Module, target: PointerTarget) -> anyhow::Result<&Value> {
+        match target {
+            PointerTarget::Local { frame, local } => self
+                .frames
+                .get(frame)
+                .and_then(|frame| frame.locals.get(&local))
+                .ok_or(anyhow::anyhow!("Local variable not found: {:?}", local)),
+            PointerTarget::Global(global) => match self.globals.get(&global) {
+                Some(value) => Ok(value),
+                None => Err(self.missing_global(module, global)),
+            },
+        }
+    }
+
+    fn variable_mut(
+        &mut self,
+        module: &Module,
+        target: PointerTarget,
+    ) -> anyhow::Result<&mut Value> {
+        match target {
+            PointerTarget::Local { frame, local } => self
+                .frames
+                .get_mut(frame)
+                .and_then(|frame| frame.locals.get_mut(&local))
+                .ok_or(anyhow::anyhow!("Local variable not found: {:?}", local)),
+            PointerTarget::Global(global) => {
+                if !self.globals.contains_key(&global) {
+                    return Err(self.missing_global(module, global));
+                }
+                Ok(self.globals.get_mut(&global).unwrap())
+            }
+        }
+    }
+
+    /// Number of elements in the runtime-sized array `pointer` refers to.
+    pub(super) fn array_length(&self, module: &Module, pointer: &Pointer) -> anyhow::Result<Value> {
+        match self.load(module, pointer)? {
+            Value::Composite { components, .. } => {
+                Ok(Value::Scalar(Scalar::U32(components.len() as u32)))
+            }
+            value => Err(anyhow::anyhow!(
+                "Invalid arrayLength of {:?}",
+                value.ty(module)
+            )),
+        }
+    }
+
+    pub(in crate::interpreter) fn load(
+        &self,
+        module: &Module,
+        pointer: &Pointer,
+    ) -> anyhow::Result<Value> {
+        self.variable(module, pointer.target)?
+            .try_load(module, &pointer.path)
+    }
+
+    pub(in crate::interpreter) fn store(
+        &mut self,
+        module: &Module,
+        pointer: &Pointer,
+        value: Value,
+    ) -> anyhow::Result<()> {
+        self.variable_mut(module, pointer.target)?
+            .try_store(module, &pointer.path, value)
+    }
+}
@@ -0,0 +1,52 @@
+use naga::{Expression, Function, Handle, Module, RelationalFunction};
+
+use crate::interpreter::{
+    value::{Scalar, Value},
+    Interpreter,
+};
+
+impl Interpreter {
+    /// `all` and `any` reduce a bool vector to a single `bool`, while `isNan`
+    /// and `isInf` test each component of a float scalar or vector.
+    pub(super) fn relational(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        fun: RelationalFunction,
+        argument: Handle<Expression>,
+    ) -> anyhow::Result<Value> {
+        let argument = self.expression(module, argument, func)?;
+        let invalid =
+            || anyhow::anyhow!("Invalid argument for {:?}: {:?}", fun, argument.ty(module));
+        match fun {
+            RelationalFunction::All | RelationalFunction::Any => {
+                let components = argument
+                    .try_components()?
+                    .iter()
+                    .map(|component| match *component {
+                        Scalar::Bool(value) => Ok(value),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                let result = match fun {
+                    RelationalFunction::All => components.iter().all(|&b| b),
+                    _ => components.iter().any(|&b| b),
+                };
+                Ok(Value::Scalar(Scalar::Bool(result)))
+            }
+            RelationalFunction::IsNan | RelationalFunction::IsInf => {
+                argument.try_map(|component| {
+                    let x = match component {
+                        Scalar::F32(x) => x as f64,
+                        Scalar::F64(x) => x,
+                        _ => return Err(invalid()),
+                    };
+                    Ok(Scalar::Bool(match fun {
+                        RelationalFunction::IsNan => x.is_nan(),
+                        _ => x.is_infinite(),
+                    }))
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use naga::{Expression, Function, Handle, Module};
+
+use crate::interpreter::{
+    value::{Scalar, Value},
+    Interpreter,
+};
+
+impl Interpreter {
+    /// Picks `accept` or `reject` as a whole for a `bool` condition, or
+    /// componentwise for a `vecN<bool>` condition.
+    pub(super) fn select(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        condition: Handle<Expression>,
+        accept: Handle<Expression>,
+        reject: Handle<Expression>,
+    ) -> anyhow::Result<Value> {
+        let condition = self.expression(module, condition, func)?;
+        let accept = self.expression(module, accept, func)?;
+        let reject = self.expression(module, reject, func)?;
+        if accept.ty(module) != reject.ty(module) {
+            return Err(anyhow::anyhow!(
+                "Invalid select: accept {:?}, reject {:?}",
+                accept.ty(module),
+                reject.ty(module)
+            ));
+        }
+
+        match condition {
+            Value::Scalar(Scalar::Bool(condition)) => {
+                if condition {
+                    Ok(accept)
+                } else {
+                    Ok(reject)
+                }
+            }
+            Value::Vector(ref conditions) => {
+                let (Value::Vector(accept), Value::Vector(reject)) = (&accept, &reject) else {
+                    return Err(anyhow::anyhow!(
+                        "Invalid select: condition {:?}, values {:?}",
+                        condition.ty(module),
+                        accept.ty(module)
+                    ));
+                };
+                if conditions.len() != accept.len() {
+                    return Err(anyhow::anyhow!(
+                        "Invalid select: condition {:?}, values {:?}",
+                        condition.ty(module),
+                        Value::Vector(accept.clone()).ty(module)
+                    ));
+                }
+                let components = conditions
+                    .iter()
+                    .zip(accept.iter().zip(reject))
+                    .map(|(condition, (&accept, &reject))| match *condition {
+                        Scalar::Bool(true) => Ok(accept),
+                        Scalar::Bool(false) => Ok(reject),
+                        _ => Err(anyhow::anyhow!(
+                            "Invalid select condition: expected bool, got {:?}",
+                            condition
+                        )),
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok(Value::Vector(components))
+            }
+            condition => Err(anyhow::anyhow!(
+                "Invalid select condition: expected bool, got {:?}",
+                condition.ty(module)
+            )),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use naga::{Expression, Function, Handle, Module, UnaryOperator};
+
+use crate::interpreter::{
+    value::{Scalar, Value},
+    Interpreter,
+};
+
+impl Interpreter {
+    pub(super) fn unary(
+        &mut self,
+        module: &Module,
+        func: &Function,
+        op: UnaryOperator,
+        expr: Handle<Expression>,
+    ) -> anyhow::Result<Value> {
+        let value = self.expression(module, expr, func)?;
+        let invalid =
+            || anyhow::anyhow!("Invalid unary expression: {:?} {:?}", op, value.ty(module));
+        value.try_map(|scalar| match (op, scalar) {
+            (UnaryOperator::Negate, Scalar::I32(x)) => Ok(Scalar::I32(x.wrapping_neg())),
+            (UnaryOperator::Negate, Scalar::F32(x)) => Ok(Scalar::F32(-x)),
+            (UnaryOperator::Negate, Scalar::F64(x)) => Ok(Scalar::F64(-x)),
+            (UnaryOperator::LogicalNot, Scalar::Bool(x)) => Ok(Scalar::Bool(!x)),
+            (UnaryOperator::BitwiseNot, Scalar::I32(x)) => Ok(Scalar::I32(!x)),
+            (UnaryOperator::BitwiseNot, Scalar::U32(x)) => Ok(Scalar::U32(!x)),
+            _ => Err(invalid()),
+        })
+    }
+}
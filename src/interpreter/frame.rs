@@ -0,0 +1,59 @@
+use naga::{Expression, Handle, LocalVariable};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::value::Value;
+
+/// Storage for a single function invocation.
+#[derive(Clone, Debug, Default)]
+pub struct Frame {
+    /// Argument values, indexed like `Function::arguments`.
+    pub arguments: Vec<Value>,
+    pub locals: FxHashMap<Handle<LocalVariable>, Value>,
+    /// Results of the expressions evaluated so far, filled in by `Emit`.
+    pub expressions: FxHashMap<Handle<Expression>, Value>,
+    /// Expressions left out of `Emit` because they are only evaluated when a
+    /// `&&` or `||` needs its right operand.
+    pub deferred: FxHashSet<Handle<Expression>>,
+    /// Arguments of the deferred calls, by their result. Such a call only runs
+    /// when its result is evaluated.
+    pub deferred_calls: FxHashMap<Handle<Expression>, Vec<Handle<Expression>>>,
+}
+
+impl Frame {
+    pub fn try_display(
+        &self,
+        module: &naga::Module,
+        func: &naga::Function,
+        pad: usize,
+    ) -> anyhow::Result<String> {
+        let mut result = String::default();
+        for (index, (arg, value)) in func.arguments.iter().zip(&self.arguments).enumerate() {
+            let name = match arg.name.as_ref() {
+                Some(name) => name.to_owned(),
+                None => format!("argument {}", index),
+            };
+            result += &format!(
+                "{}{}: {}\n",
+                " ".repeat(pad),
+                name,
+                value.try_display(module)?
+            );
+        }
+        for (handle, local) in func.local_variables.iter() {
+            let Some(value) = self.locals.get(&handle) else {
+                continue;
+            };
+            let name = match local.name.as_ref() {
+                Some(name) => name.to_owned(),
+                None => format!("{:?}", handle),
+            };
+            result += &format!(
+                "{}{}: {}\n",
+                " ".repeat(pad),
+                name,
+                value.try_display(module)?
+            );
+        }
+        Ok(result)
+    }
+}
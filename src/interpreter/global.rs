@@ -0,0 +1,104 @@
+use naga::{AddressSpace, GlobalVariable, Handle, Module, ResourceBinding, StorageAccess};
+
+use super::{expression::constant::const_expression, value::Value, Interpreter};
+
+impl Interpreter {
+    /// Binds `data` as the contents of the uniform or storage buffer at
+    /// `binding`. Storage buffers written by the shader are updated in place
+    /// after each run.
+    pub fn bind_buffer(&mut self, binding: ResourceBinding, data: Vec<u8>) {
+        self.buffers.insert(binding, data);
+    }
+
+    /// The current contents of the buffer bound at `binding`.
+    pub fn buffer(&self, binding: &ResourceBinding) -> Option<&[u8]> {
+        self.buffers.get(binding).map(Vec::as_slice)
+    }
+
+    /// Zeroes the module's workgroup variables, which are then shared by
+    /// every invocation until the module is loaded again.
+    pub(super) fn init_workgroup_globals(&mut self, module: &Module) -> anyhow::Result<()> {
+        for (handle, global) in module.global_variables.iter() {
+            if global.space == AddressSpace::WorkGroup {
+                self.globals
+                    .insert(handle, Value::zeroed(module, global.ty)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets up the globals for a new invocation: private variables are reset
+    /// to their initializers and buffers are read from their bindings.
+    pub(super) fn init_globals(&mut self, module: &Module) -> anyhow::Result<()> {
+        for (handle, global) in module.global_variables.iter() {
+            let value = match global.space {
+                AddressSpace::Private => match global.init {
+                    Some(init) => const_expression(module, init)?,
+                    None => Value::zeroed(module, global.ty)?,
+                },
+                AddressSpace::Uniform | AddressSpace::Storage { .. } => {
+                    let Some(data) = global.binding.as_ref().and_then(|b| self.buffers.get(b))
+                    else {
+                        continue;
+                    };
+                    Value::from_bytes(module, global.ty, data)?
+                }
+                _ => continue,
+            };
+            self.globals.insert(handle, value);
+        }
+        Ok(())
+    }
+
+    /// Copies the storage buffers the shader may have written back to their
+    /// bindings, printing their new contents.
+    pub(super) fn write_back_globals(&mut self, module: &Module) -> anyhow::Result<()> {
+        for (handle, global) in module.global_variables.iter() {
+            let AddressSpace::Storage { access } = global.space else {
+                continue;
+            };
+            if !access.contains(StorageAccess::STORE) {
+                continue;
+            }
+            let (Some(binding), Some(value)) = (global.binding.as_ref(), self.globals.get(&handle))
+            else {
+                continue;
+            };
+            println!(
+                "Buffer `{}` @group({}) @binding({}): {}",
+                global.name.as_deref().unwrap_or("<unnamed>"),
+                binding.group,
+                binding.binding,
+                value.try_display(module)?
+            );
+            self.buffers
+                .insert(binding.clone(), value.to_bytes(module)?);
+        }
+        Ok(())
+    }
+
+    /// Explains why a global has no storage.
+    pub(super) fn missing_global(
+        &self,
+        module: &Module,
+        handle: Handle<GlobalVariable>,
+    ) -> anyhow::Error {
+        let global = &module.global_variables[handle];
+        let name = global.name.as_deref().unwrap_or("<unnamed>");
+        match (global.space, global.binding.as_ref()) {
+            (AddressSpace::Uniform | AddressSpace::Storage { .. }, Some(binding)) => {
+                anyhow::anyhow!(
+                    "No buffer is bound to @group({}) @binding({}) for `{}`",
+                    binding.group,
+                    binding.binding,
+                    name
+                )
+            }
+            (space, _) => anyhow::anyhow!(
+                "Global variable `{}` in the {:?} address space is not supported",
+                name,
+                space
+            ),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use naga::{Binding, Handle, Module, ScalarKind, Type, TypeInner};
+
+use super::{
+    binding_name, type_name,
+    value::{Scalar, Value},
+};
+
+/// Prompts on stdin for a value of type `ty`. Structs are read one member at
+/// a time.
+pub fn prompt_value(
+    module: &Module,
+    name: &str,
+    binding: Option<&Binding>,
+    ty: Handle<Type>,
+) -> anyhow::Result<Value> {
+    let handle = ty;
+    let ty = module
+        .types
+        .get_handle(ty)
+        .map_err(|_| anyhow::anyhow!("Invalid type handle: {:?}", ty))?;
+
+    let value = match ty.inner {
+        TypeInner::Struct { ref members, .. } => {
+            let mut components = vec![];
+            for (index, member) in members.iter().enumerate() {
+                let member_name = match member.name.as_ref() {
+                    Some(member_name) => format!("{}.{}", name, member_name),
+                    None => format!("{}.{}", name, index),
+                };
+                components.push(prompt_value(
+                    module,
+                    &member_name,
+                    member.binding.as_ref(),
+                    member.ty,
+                )?);
+            }
+            Value::Composite {
+                ty: handle,
+                components,
+            }
+        }
+        _ => {
+            let ty_name = type_name(module, ty).ok_or(anyhow::anyhow!("Invalid type: {:?}", ty))?;
+            match binding {
+                Some(binding) => println!(
+                    "Enter value for argument `{}` {} ({}):",
+                    name,
+                    binding_name(binding),
+                    ty_name
+                ),
+                None => println!("Enter value for argument `{}` ({}):", name, ty_name),
+            }
+            let mut value = String::default();
+            std::io::stdin().read_line(&mut value)?;
+            parse_value(&ty.inner, value.trim())?
+        }
+    };
+
+    Ok(value)
+}
+
+/// Parses a scalar, or a comma-separated list of vector components or
+/// column-major matrix components.
+fn parse_value(ty: &TypeInner, value: &str) -> anyhow::Result<Value> {
+    let value = match *ty {
+        TypeInner::Scalar { kind, width } => Value::Scalar(Scalar::parse(kind, width, value)?),
+        TypeInner::Vector { size, kind, width } => {
+            let components = value
+                .split(',')
+                .map(|value| Scalar::parse(kind, width, value))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            if components.len() != size as usize {
+                return Err(anyhow::anyhow!(
+                    "Invalid vector: expected {} components, got {}",
+                    size as usize,
+                    components.len()
+                ));
+            }
+            Value::Vector(components)
+        }
+        TypeInner::Matrix {
+            columns,
+            rows,
+            width,
+        } => {
+            let value = value.replace(['[', ']'], "");
+            let values = value.split(',').collect::<Vec<_>>();
+            if values.len() != columns as usize * rows as usize {
+                return Err(anyhow::anyhow!(
+                    "Invalid matrix: expected {} components, got {}",
+                    columns as usize * rows as usize,
+                    values.len()
+                ));
+            }
+            Value::Matrix(
+                values
+                    .chunks(rows as usize)
+                    .map(|column| {
+                        column
+                            .iter()
+                            .map(|value| Scalar::parse(ScalarKind::Float, width, value))
+                            .collect()
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            )
+        }
+        _ => todo!("{:?}", ty),
+    };
+    Ok(value)
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use naga::{ArraySize, Handle, Module, ScalarKind, Type, TypeInner};
+use serde::Deserialize;
+
+use super::{
+    value::{Scalar, Value},
+    Interpreter,
+};
+
+/// The contents of one entry in a bindings manifest.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Data {
+    Bool(bool),
+    Number(f64),
+    /// Path to a raw binary file, relative to the manifest.
+    File(PathBuf),
+    /// Vector components, matrix columns, array elements or struct members
+    /// in order.
+    List(Vec<Data>),
+    /// Struct members by name.
+    Members(BTreeMap<String, Data>),
+}
+
+impl Interpreter {
+    /// Binds the buffers described by a JSON or RON manifest, which maps the
+    /// names of uniform and storage globals either to their contents, laid
+    /// out against the global's type, or to a raw `.bin` file.
+    pub fn bind_manifest(&mut self, module: &Module, path: &Path) -> anyhow::Result<()> {
+        let source = std::fs::read_to_string(path)?;
+        let manifest: BTreeMap<String, Data> = match path.extension() {
+            Some(ext) if ext == "ron" => ron::from_str(&source)?,
+            _ => serde_json::from_str(&source)?,
+        };
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        for (name, data) in manifest {
+            let global = module
+                .global_variables
+                .iter()
+                .map(|(_, global)| global)
+                .find(|global| global.name.as_deref() == Some(name.as_str()))
+                .ok_or(anyhow::anyhow!("No global variable named `{}`", name))?;
+            let binding = global.binding.clone().ok_or(anyhow::anyhow!(
+                "Global variable `{}` is not a resource binding",
+                name
+            ))?;
+            let bytes = match data {
+                Data::File(file) => {
+                    let bytes = std::fs::read(dir.join(&file))?;
+                    let size = module.types[global.ty].inner.size(module.to_ctx()) as usize;
+                    if bytes.len() < size {
+                        return Err(anyhow::anyhow!(
+                            "`{}` needs at least {} bytes, but {} has {}",
+                            name,
+                            size,
+                            file.display(),
+                            bytes.len()
+                        ));
+                    }
+                    bytes
+                }
+                data => value(module, &name, global.ty, &data)?.to_bytes(module)?,
+            };
+            self.bind_buffer(binding, bytes);
+        }
+        Ok(())
+    }
+}
+
+/// Lays out manifest data as a value of type `ty`. `name` locates the data in
+/// error messages.
+fn value(module: &Module, name: &str, ty: Handle<Type>, data: &Data) -> anyhow::Result<Value> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid value for `{}`: expected {:?}, got {:?}",
+            name,
+            module.types[ty].inner,
+            data
+        )
+    };
+    let list = |len: Option<usize>| match *data {
+        Data::List(ref items) if len.is_none_or(|len| len == items.len()) => Ok(items),
+        _ => Err(invalid()),
+    };
+    let value = match module.types[ty].inner {
+        TypeInner::Scalar { kind, width } | TypeInner::Atomic { kind, width } => {
+            Value::Scalar(scalar(kind, width, data).ok_or_else(invalid)?)
+        }
+        TypeInner::Vector { size, kind, width } => Value::Vector(
+            list(Some(size as usize))?
+                .iter()
+                .map(|item| scalar(kind, width, item).ok_or_else(invalid))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        TypeInner::Matrix {
+            columns,
+            rows,
+            width,
+        } => Value::Matrix(
+            list(Some(columns as usize))?
+                .iter()
+                .map(|column| match *column {
+                    Data::List(ref column) if column.len() == rows as usize => column
+                        .iter()
+                        .map(|item| scalar(ScalarKind::Float, width, item).ok_or_else(invalid))
+                        .collect(),
+                    _ => Err(invalid()),
+                })
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        TypeInner::Array { base, size, .. } => {
+            let len = match size {
+                ArraySize::Constant(size) => Some(size.get() as usize),
+                ArraySize::Dynamic => None,
+            };
+            let components = list(len)?
+                .iter()
+                .enumerate()
+                .map(|(index, item)| value(module, &format!("{}[{}]", name, index), base, item))
+                .collect::<anyhow::Result<_>>()?;
+            Value::Composite { ty, components }
+        }
+        TypeInner::Struct { ref members, .. } => {
+            let items = match *data {
+                Data::List(ref items) if items.len() == members.len() => {
+                    items.iter().collect::<Vec<_>>()
+                }
+                Data::Members(ref items) => {
+                    if let Some(unknown) = items
+                        .keys()
+                        .find(|key| !members.iter().any(|m| m.name.as_ref() == Some(key)))
+                    {
+                        return Err(anyhow::anyhow!("`{}` has no member `{}`", name, unknown));
+                    }
+                    members
+                        .iter()
+                        .map(|member| {
+                            member
+                                .name
+                                .as_ref()
+                                .and_then(|member| items.get(member))
+                                .ok_or(anyhow::anyhow!(
+                                    "Missing member `{}` of `{}`",
+                                    member.name.as_deref().unwrap_or("<unnamed>"),
+                                    name
+                                ))
+                        })
+                        .collect::<anyhow::Result<_>>()?
+                }
+                _ => return Err(invalid()),
+            };
+            let components = members
+                .iter()
+                .zip(items)
+                .enumerate()
+                .map(|(index, (member, item))| {
+                    let name = match member.name.as_ref() {
+                        Some(member) => format!("{}.{}", name, member),
+                        None => format!("{}.{}", name, index),
+                    };
+                    value(module, &name, member.ty, item)
+                })
+                .collect::<anyhow::Result<_>>()?;
+            Value::Composite { ty, components }
+        }
+        ref ty => {
+            return Err(anyhow::anyhow!(
+                "Unsupported binding type for `{}`: {:?}",
+                name,
+                ty
+            ))
+        }
+    };
+    Ok(value)
+}
+
+fn scalar(kind: ScalarKind, width: naga::Bytes, data: &Data) -> Option<Scalar> {
+    match *data {
+        Data::Bool(value) => Scalar::parse(kind, width, &value.to_string()).ok(),
+        Data::Number(value) => Scalar::parse(kind, width, &value.to_string()).ok(),
+        _ => None,
+    }
+}
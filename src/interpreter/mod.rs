@@ -1,14 +1,21 @@
-use naga::{ShaderStage, VectorSize};
+use naga::{Constant, GlobalVariable, Handle, ResourceBinding, ShaderStage, VectorSize};
 use rustc_hash::FxHashMap;
 
-use self::{scope::Scope, value::Value};
+use self::{frame::Frame, statement::ControlFlow, value::Value};
+
+/// Maximum number of nested function calls. WGSL forbids recursion, so this
+/// is only reached by malformed modules.
+pub const MAX_CALL_DEPTH: usize = 256;
 
 pub mod expression;
-pub mod scope;
+pub mod frame;
+pub mod global;
+pub mod input;
+pub mod manifest;
 pub mod statement;
 pub mod value;
 
-fn type_name(ty: &naga::Type) -> Option<String> {
+fn type_name(module: &naga::Module, ty: &naga::Type) -> Option<String> {
     match ty.name.as_ref() {
         Some(name) => Some(name.to_owned()),
         None => match ty.inner {
@@ -22,28 +29,107 @@ fn type_name(ty: &naga::Type) -> Option<String> {
                 let width = width * 8;
                 Some(format!("vec{}<{:?}{}>", size, kind, width))
             }
+            naga::TypeInner::Matrix {
+                columns,
+                rows,
+                width,
+            } => {
+                let width = width * 8;
+                Some(format!(
+                    "mat{}x{}<Float{}>",
+                    columns as u8, rows as u8, width
+                ))
+            }
+            naga::TypeInner::Struct { .. } => Some("struct".to_owned()),
+            naga::TypeInner::Array { base, size, .. } => {
+                let base = type_name(module, &module.types[base])?;
+                match size {
+                    naga::ArraySize::Constant(size) => Some(format!("array<{}, {}>", base, size)),
+                    naga::ArraySize::Dynamic => Some(format!("array<{}>", base)),
+                }
+            }
             _ => todo!("{:?}", ty),
         },
     }
 }
 
-pub struct Interpreter<'a> {
+/// Formats a binding the way it is written in WGSL, e.g. `@location(0)`.
+fn binding_name(binding: &naga::Binding) -> String {
+    match binding {
+        naga::Binding::Location { location, .. } => format!("@location({})", location),
+        naga::Binding::BuiltIn(builtin) => {
+            let name = match builtin {
+                naga::BuiltIn::Position { .. } => "position",
+                naga::BuiltIn::ViewIndex => "view_index",
+                naga::BuiltIn::InstanceIndex => "instance_index",
+                naga::BuiltIn::VertexIndex => "vertex_index",
+                naga::BuiltIn::FragDepth => "frag_depth",
+                naga::BuiltIn::FrontFacing => "front_facing",
+                naga::BuiltIn::PrimitiveIndex => "primitive_index",
+                naga::BuiltIn::SampleIndex => "sample_index",
+                naga::BuiltIn::SampleMask => "sample_mask",
+                naga::BuiltIn::GlobalInvocationId => "global_invocation_id",
+                naga::BuiltIn::LocalInvocationId => "local_invocation_id",
+                naga::BuiltIn::LocalInvocationIndex => "local_invocation_index",
+                naga::BuiltIn::WorkGroupId => "workgroup_id",
+                naga::BuiltIn::NumWorkGroups => "num_workgroups",
+                builtin => return format!("@builtin({:?})", builtin),
+            };
+            format!("@builtin({})", name)
+        }
+    }
+}
+
+pub struct Interpreter {
     shader_stage: ShaderStage,
     verbosity: u8,
 
-    scopes: Vec<Scope<'a>>,
+    frames: Vec<Frame>,
+    constants: FxHashMap<Handle<Constant>, Value>,
+    globals: FxHashMap<Handle<GlobalVariable>, Value>,
+    buffers: FxHashMap<ResourceBinding, Vec<u8>>,
+    /// Whether `load_module` has been called.
+    loaded: bool,
 }
 
-impl<'a> Interpreter<'a> {
+impl Interpreter {
     pub fn new(shader_stage: ShaderStage, verbosity: u8) -> Self {
         Self {
             shader_stage,
             verbosity,
-            scopes: vec![],
+            frames: vec![],
+            constants: FxHashMap::default(),
+            globals: FxHashMap::default(),
+            buffers: FxHashMap::default(),
+            loaded: false,
         }
     }
 
-    pub fn run(&mut self, module: &'a naga::Module) -> anyhow::Result<()> {
+    /// Evaluates the module's constants once, so every invocation can share
+    /// them, and zeroes its workgroup variables.
+    pub fn load_module(&mut self, module: &naga::Module) -> anyhow::Result<()> {
+        self.constants = module
+            .constants
+            .iter()
+            .map(|(handle, constant)| {
+                Ok((
+                    handle,
+                    expression::constant::const_expression(module, constant.init)?,
+                ))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        self.globals.clear();
+        self.init_workgroup_globals(module)?;
+        self.loaded = true;
+        Ok(())
+    }
+
+    /// Runs the module's entry point for the interpreter's stage, loading
+    /// the module first if `load_module` has not been called.
+    pub fn run(&mut self, module: &naga::Module) -> anyhow::Result<()> {
+        if !self.loaded {
+            self.load_module(module)?;
+        }
         let entry_point = module
             .entry_points
             .iter()
@@ -56,135 +142,112 @@ impl<'a> Interpreter<'a> {
         println!("Entry point: {}", entry_point.name);
         println!("Stage: {:?}", entry_point.stage);
 
-        let body = entry_point.function.body.as_ref().to_owned();
         let args = entry_point
             .function
             .arguments
             .iter()
-            .map(|arg| {
-                let name = arg
-                    .name
-                    .as_ref()
-                    .ok_or(anyhow::anyhow!("Argument has no name"))?;
-
-                let ty = module
-                    .types
-                    .get_handle(arg.ty)
-                    .map_err(|_| anyhow::anyhow!("Invalid type handle: {:?}", arg.ty))?;
-
-                let ty_name = type_name(ty).ok_or(anyhow::anyhow!("Invalid type: {:?}", ty))?;
-
-                let size = ty.inner.size(module.to_ctx());
-
-                let value = {
-                    println!("Enter value for argument `{}` ({}):", name, ty_name);
-                    let mut value = String::default();
-                    std::io::stdin().read_line(&mut value)?;
-                    let value = value.trim();
-                    match ty.inner {
-                        naga::TypeInner::Scalar { kind, width } => match kind {
-                            naga::ScalarKind::Sint => match width {
-                                4 => Value::from_pod(&ty.inner, value.parse::<i32>()?),
-                                _ => todo!("{:?}", width),
-                            },
-                            naga::ScalarKind::Uint => match width {
-                                4 => Value::from_pod(&ty.inner, value.parse::<u32>()?),
-                                _ => todo!("{:?}", width),
-                            },
-                            naga::ScalarKind::Float => match width {
-                                4 => Value::from_pod(&ty.inner, value.parse::<f32>()?),
-                                _ => todo!("{:?}", width),
-                            },
-                            naga::ScalarKind::Bool => {
-                                Value::from_pod(&ty.inner, value.parse::<bool>()? as u8)
-                            }
-                        },
-                        naga::TypeInner::Vector { size, kind, width } => {
-                            let size = size as usize;
-                            let width = width as usize;
-                            let mut data = vec![0; size * width];
-                            for (i, value) in value.split(',').enumerate() {
-                                match kind {
-                                    naga::ScalarKind::Sint => match width {
-                                        4 => {
-                                            let value = value.trim().parse::<i32>()?;
-                                            data[i * width..(i + 1) * width]
-                                                .copy_from_slice(bytemuck::bytes_of(&value));
-                                        }
-                                        _ => todo!("{:?}", width),
-                                    },
-                                    naga::ScalarKind::Uint => match width {
-                                        4 => {
-                                            let value = value.trim().parse::<u32>()?;
-                                            data[i * width..(i + 1) * width]
-                                                .copy_from_slice(bytemuck::bytes_of(&value));
-                                        }
-                                        _ => todo!("{:?}", width),
-                                    },
-                                    naga::ScalarKind::Float => match width {
-                                        4 => {
-                                            let value = value.trim().parse::<f32>()?;
-                                            data[i * width..(i + 1) * width]
-                                                .copy_from_slice(bytemuck::bytes_of(&value));
-                                        }
-                                        _ => todo!("{:?}", width),
-                                    },
-                                    naga::ScalarKind::Bool => {
-                                        let value = value.trim().parse::<bool>()?;
-                                        data[i * width..(i + 1) * width]
-                                            .copy_from_slice(bytemuck::bytes_of(&(value as u8)));
-                                    }
-                                }
-                            }
-                            Value::from_data(&ty.inner, data)
-                        }
-                        _ => todo!("{:?}", ty.inner),
-                    }
+            .enumerate()
+            .map(|(index, arg)| {
+                let name = match arg.name.as_ref() {
+                    Some(name) => name.to_owned(),
+                    None => format!("argument {}", index),
                 };
-
-                if size as usize != value.data.len() {
-                    return Err(anyhow::anyhow!(
-                        "Invalid argument size: expected {}, got {}",
-                        size,
-                        value.data.len()
-                    ));
-                }
-
-                Ok((name.to_owned(), value))
+                input::prompt_value(module, &name, arg.binding.as_ref(), arg.ty)
             })
-            .collect::<anyhow::Result<FxHashMap<_, _>>>()?;
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
-        let mut value = None;
-        self.push_scope(args);
-        for stmt in body {
-            value = self.statement(module, stmt, &entry_point.function)?;
-        }
-        self.pop_scope();
+        self.init_globals(module)?;
+        let flow = self.call(module, &entry_point.function, args)?;
+        self.write_back_globals(module)?;
 
-        if let Some(value) = value {
-            println!("Result: {}", value.try_display()?);
+        match flow {
+            ControlFlow::Return(Some(value)) => println!("Result: {}", value.try_display(module)?),
+            ControlFlow::Kill => println!("Invocation discarded"),
+            _ => {}
         }
 
         Ok(())
     }
 
-    fn push_scope(&mut self, mut add_values: FxHashMap<String, Value<'a>>) {
-        // inherit values from previous scope
-        if let Some(scope) = self.scopes.last() {
-            for (name, value) in scope.variables.iter() {
-                add_values.insert(name.to_owned(), value.to_owned());
-            }
+    /// Runs `func` in a new frame with the given arguments. Returns either
+    /// `ControlFlow::Return` with the function's result, or
+    /// `ControlFlow::Kill` if the invocation was discarded.
+    fn call(
+        &mut self,
+        module: &naga::Module,
+        func: &naga::Function,
+        args: Vec<Value>,
+    ) -> anyhow::Result<ControlFlow> {
+        if args.len() != func.arguments.len() {
+            return Err(anyhow::anyhow!(
+                "Invalid call to `{}`: expected {} arguments, got {}",
+                func.name.as_deref().unwrap_or("<unnamed>"),
+                func.arguments.len(),
+                args.len()
+            ));
+        }
+
+        if self.frames.len() >= MAX_CALL_DEPTH {
+            return Err(anyhow::anyhow!(
+                "Maximum call depth of {} exceeded while calling `{}` (recursion is not allowed)",
+                MAX_CALL_DEPTH,
+                func.name.as_deref().unwrap_or("<unnamed>")
+            ));
         }
-        self.scopes.push(Scope {
-            variables: add_values,
+
+        self.push_frame(module, func, args)?;
+        let flow = self.block(module, &func.body, func);
+        self.pop_frame();
+
+        match flow? {
+            flow @ (ControlFlow::Return(_) | ControlFlow::Kill) => Ok(flow),
+            ControlFlow::Normal => Ok(ControlFlow::Return(None)),
+            flow @ (ControlFlow::Break | ControlFlow::Continue) => Err(anyhow::anyhow!(
+                "Invalid control flow outside of a loop or switch: {:?}",
+                flow
+            )),
+        }
+    }
+
+    /// Pushes a new frame for `func` with its arguments bound to `args` and
+    /// its local variables set to their initializers or zeroed.
+    fn push_frame(
+        &mut self,
+        module: &naga::Module,
+        func: &naga::Function,
+        args: Vec<Value>,
+    ) -> anyhow::Result<()> {
+        let mut calls = statement::calls(&func.body);
+        let deferred = statement::deferred(func, &calls);
+        calls.retain(|result, _| deferred.contains(result));
+        self.frames.push(Frame {
+            arguments: args,
+            deferred,
+            deferred_calls: calls,
+            ..Default::default()
         });
+        for (handle, local) in func.local_variables.iter() {
+            let value = match local.init {
+                Some(init) => self.expression(module, init, func)?,
+                None => Value::zeroed(module, local.ty)?,
+            };
+            self.current_frame_mut()
+                .ok_or(anyhow::anyhow!("No frame found"))?
+                .locals
+                .insert(handle, value);
+        }
+        Ok(())
+    }
+
+    fn pop_frame(&mut self) -> Option<Frame> {
+        self.frames.pop()
     }
 
-    fn pop_scope(&mut self) -> Option<Scope<'a>> {
-        self.scopes.pop()
+    fn current_frame(&self) -> Option<&Frame> {
+        self.frames.last()
     }
 
-    fn current_scope(&mut self) -> Option<&mut Scope<'a>> {
-        self.scopes.last_mut()
+    fn current_frame_mut(&mut self) -> Option<&mut Frame> {
+        self.frames.last_mut()
     }
 }
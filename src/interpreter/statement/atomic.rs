@@ -0,0 +1,86 @@
+use naga::{AtomicFunction, BinaryOperator, Expression, Handle, Module};
+
+use crate::interpreter::{
+    expression::binary::scalar_binary,
+    value::{Scalar, Value},
+    Interpreter,
+};
+
+use super::ControlFlow;
+
+impl Interpreter {
+    /// Applies `fun` to the scalar behind `pointer` and caches the value it
+    /// held before as `result`. For a compare-exchange, `result` also records
+    /// whether the value was exchanged.
+    pub(super) fn atomic(
+        &mut self,
+        module: &Module,
+        func: &naga::Function,
+        pointer: Handle<Expression>,
+        fun: &AtomicFunction,
+        value: Handle<Expression>,
+        result: Handle<Expression>,
+    ) -> anyhow::Result<ControlFlow> {
+        let pointer = self.pointer(module, pointer, func)?;
+        let old = scalar(module, self.load(module, &pointer)?)?;
+        let value = scalar(module, self.expression(module, value, func)?)?;
+        let invalid = || {
+            anyhow::anyhow!(
+                "Invalid atomic operation: {:?} on {:?} and {:?}",
+                fun,
+                old,
+                value
+            )
+        };
+
+        let mut exchanged = None;
+        let new = match *fun {
+            AtomicFunction::Add => scalar_binary(BinaryOperator::Add, old, value),
+            AtomicFunction::Subtract => scalar_binary(BinaryOperator::Subtract, old, value),
+            AtomicFunction::And => scalar_binary(BinaryOperator::And, old, value),
+            AtomicFunction::ExclusiveOr => scalar_binary(BinaryOperator::ExclusiveOr, old, value),
+            AtomicFunction::InclusiveOr => scalar_binary(BinaryOperator::InclusiveOr, old, value),
+            AtomicFunction::Min => match scalar_binary(BinaryOperator::Less, value, old) {
+                Some(Scalar::Bool(less)) => Some(if less { value } else { old }),
+                _ => None,
+            },
+            AtomicFunction::Max => match scalar_binary(BinaryOperator::Greater, value, old) {
+                Some(Scalar::Bool(greater)) => Some(if greater { value } else { old }),
+                _ => None,
+            },
+            AtomicFunction::Exchange { compare: None } => Some(value),
+            AtomicFunction::Exchange {
+                compare: Some(compare),
+            } => {
+                let compare = scalar(module, self.expression(module, compare, func)?)?;
+                exchanged = Some(old == compare);
+                Some(if old == compare { value } else { old })
+            }
+        };
+        let new = new.ok_or_else(invalid)?;
+        self.store(module, &pointer, Value::Scalar(new))?;
+
+        let result_value = match (exchanged, &func.expressions[result]) {
+            (Some(exchanged), &Expression::AtomicResult { ty, .. }) => Value::Composite {
+                ty,
+                components: vec![Value::Scalar(old), Value::Scalar(Scalar::Bool(exchanged))],
+            },
+            _ => Value::Scalar(old),
+        };
+        self.current_frame_mut()
+            .ok_or(anyhow::anyhow!("No frame found"))?
+            .expressions
+            .insert(result, result_value);
+        Ok(ControlFlow::Normal)
+    }
+}
+
+fn scalar(module: &Module, value: Value) -> anyhow::Result<Scalar> {
+    match value {
+        Value::Scalar(scalar) => Ok(scalar),
+        value => Err(anyhow::anyhow!(
+            "Invalid atomic operand: expected a scalar, got {:?}",
+            value.ty(module)
+        )),
+    }
+}
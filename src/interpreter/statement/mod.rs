@@ -1,47 +1,438 @@
-use naga::{Module, Statement};
-use rustc_hash::FxHashMap;
+use naga::{BinaryOperator, Block, Expression, Function, Handle, Module, Statement, SwitchValue};
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use super::{Interpreter, Value};
+use super::{value::Scalar, Interpreter, Value};
 
-impl<'a> Interpreter<'a> {
+pub mod atomic;
+
+/// How execution should continue after a statement has run.
+#[derive(Clone, Debug)]
+pub enum ControlFlow {
+    /// Continue with the next statement.
+    Normal,
+    /// Exit the innermost loop or switch.
+    Break,
+    /// Skip to the `continuing` block of the innermost loop.
+    Continue,
+    /// Return from the current function, with an optional result.
+    Return(Option<Value>),
+    /// Discard the current invocation.
+    Kill,
+}
+
+impl Interpreter {
     pub(super) fn statement(
         &mut self,
-        module: &'a Module,
-        stmt: Statement,
+        module: &Module,
+        stmt: &Statement,
         func: &naga::Function,
-    ) -> anyhow::Result<Option<Value<'a>>> {
+    ) -> anyhow::Result<ControlFlow> {
         if self.verbosity > 0 {
             println!("Statement: {:?}", stmt);
         }
         if self.verbosity > 0 {
-            if let Some(scope) = self.current_scope() {
-                println!("Variables:\n{}", scope.try_display(2)?);
+            if let Some(frame) = self.current_frame() {
+                println!("Variables:\n{}", frame.try_display(module, func, 2)?);
             }
         }
         match stmt {
             Statement::Emit(exprs) => {
-                exprs
-                    .into_iter()
-                    .map(|expr| self.expression(module, expr, func))
-                    .collect::<anyhow::Result<Vec<_>>>()?;
-                Ok(None)
+                for expr in exprs.clone() {
+                    let frame = self
+                        .current_frame_mut()
+                        .ok_or(anyhow::anyhow!("No frame found"))?;
+                    if frame.deferred.contains(&expr) {
+                        // evaluated on demand by `logical`, so drop any result
+                        // left over from a previous iteration
+                        frame.expressions.remove(&expr);
+                    } else {
+                        self.emit(module, expr, func)?;
+                    }
+                }
+                Ok(ControlFlow::Normal)
+            }
+            Statement::Block(block) => self.block(module, block, func),
+            Statement::If {
+                condition,
+                accept,
+                reject,
+            } => {
+                if self.condition(module, *condition, func)? {
+                    self.block(module, accept, func)
+                } else {
+                    self.block(module, reject, func)
+                }
             }
-            Statement::Block(block) => {
-                self.push_scope(FxHashMap::default());
-                let mut value = None;
-                for stmt in block.into_iter() {
-                    value = self.statement(module, stmt.to_owned(), func)?;
+            Statement::Switch { selector, cases } => {
+                let selector = self.expression(module, *selector, func)?;
+                let selector = match selector {
+                    Value::Scalar(Scalar::I32(value)) => SwitchValue::I32(value),
+                    Value::Scalar(Scalar::U32(value)) => SwitchValue::U32(value),
+                    value => {
+                        return Err(anyhow::anyhow!(
+                            "Invalid switch selector: expected i32 or u32, got {:?}",
+                            value.ty(module)
+                        ))
+                    }
+                };
+                let start = cases
+                    .iter()
+                    .position(|case| case.value == selector)
+                    .or_else(|| {
+                        cases
+                            .iter()
+                            .position(|case| case.value == SwitchValue::Default)
+                    });
+                let Some(start) = start else {
+                    return Ok(ControlFlow::Normal);
+                };
+                for case in &cases[start..] {
+                    match self.block(module, &case.body, func)? {
+                        ControlFlow::Normal => {}
+                        ControlFlow::Break => return Ok(ControlFlow::Normal),
+                        flow => return Ok(flow),
+                    }
+                    if !case.fall_through {
+                        break;
+                    }
                 }
-                self.pop_scope();
-                Ok(value)
+                Ok(ControlFlow::Normal)
             }
+            Statement::Loop {
+                body,
+                continuing,
+                break_if,
+            } => loop {
+                match self.block(module, body, func)? {
+                    ControlFlow::Normal | ControlFlow::Continue => {}
+                    ControlFlow::Break => return Ok(ControlFlow::Normal),
+                    flow => return Ok(flow),
+                }
+                match self.block(module, continuing, func)? {
+                    ControlFlow::Normal => {}
+                    flow @ (ControlFlow::Return(_) | ControlFlow::Kill) => return Ok(flow),
+                    flow => {
+                        return Err(anyhow::anyhow!(
+                            "Invalid control flow in continuing block: {:?}",
+                            flow
+                        ))
+                    }
+                }
+                if let Some(break_if) = break_if {
+                    if self.condition(module, *break_if, func)? {
+                        return Ok(ControlFlow::Normal);
+                    }
+                }
+            },
+            Statement::Break => Ok(ControlFlow::Break),
+            Statement::Continue => Ok(ControlFlow::Continue),
             Statement::Return { value } => {
                 let value = value
                     .map(|expr| self.expression(module, expr, func))
                     .transpose()?;
-                Ok(value)
+                Ok(ControlFlow::Return(value))
+            }
+            Statement::Kill => Ok(ControlFlow::Kill),
+            Statement::Call {
+                function,
+                arguments,
+                result,
+            } => {
+                if let Some(result) = result {
+                    let frame = self
+                        .current_frame_mut()
+                        .ok_or(anyhow::anyhow!("No frame found"))?;
+                    if frame.deferred.contains(result) {
+                        // run on demand by `logical`, like the expressions
+                        // its result feeds
+                        frame.expressions.remove(result);
+                        return Ok(ControlFlow::Normal);
+                    }
+                }
+                let value = match self.call_statement(module, func, *function, arguments)? {
+                    ControlFlow::Return(value) => value,
+                    flow => return Ok(flow),
+                };
+                if let Some(result) = result {
+                    let value = value.ok_or_else(|| no_result(module, *function))?;
+                    self.current_frame_mut()
+                        .ok_or(anyhow::anyhow!("No frame found"))?
+                        .expressions
+                        .insert(*result, value);
+                }
+                Ok(ControlFlow::Normal)
+            }
+            Statement::Store { pointer, value } => {
+                let pointer = self.pointer(module, *pointer, func)?;
+                let value = self.expression(module, *value, func)?;
+                self.store(module, &pointer, value)?;
+                Ok(ControlFlow::Normal)
             }
+            Statement::Atomic {
+                pointer,
+                fun,
+                value,
+                result,
+            } => self.atomic(module, func, *pointer, fun, *value, *result),
+            // invocations run one at a time, so there is nothing to wait for
+            Statement::Barrier(_) => Ok(ControlFlow::Normal),
             stmt => todo!("{:?}", stmt),
         }
     }
+
+    /// Runs the statements of `block`, stopping at the first one that
+    /// transfers control elsewhere.
+    pub(super) fn block(
+        &mut self,
+        module: &Module,
+        block: &Block,
+        func: &naga::Function,
+    ) -> anyhow::Result<ControlFlow> {
+        let mut flow = ControlFlow::Normal;
+        for stmt in block.iter() {
+            flow = self.statement(module, stmt, func)?;
+            if !matches!(flow, ControlFlow::Normal) {
+                break;
+            }
+        }
+        Ok(flow)
+    }
+
+    /// Evaluates the arguments of a call and runs the callee.
+    fn call_statement(
+        &mut self,
+        module: &Module,
+        func: &naga::Function,
+        function: Handle<Function>,
+        arguments: &[Handle<Expression>],
+    ) -> anyhow::Result<ControlFlow> {
+        let args = arguments
+            .iter()
+            .map(|expr| self.expression(module, *expr, func))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.call(module, &module.functions[function], args)
+    }
+
+    /// Runs the deferred call that produces `result` and returns its value.
+    pub(super) fn deferred_call(
+        &mut self,
+        module: &Module,
+        func: &naga::Function,
+        function: Handle<Function>,
+        result: Handle<Expression>,
+    ) -> anyhow::Result<Value> {
+        let arguments = self
+            .current_frame()
+            .and_then(|frame| frame.deferred_calls.get(&result))
+            .cloned()
+            .ok_or(anyhow::anyhow!(
+                "Result of call to {:?} used before the call",
+                function
+            ))?;
+        match self.call_statement(module, func, function, &arguments)? {
+            ControlFlow::Return(Some(value)) => Ok(value),
+            ControlFlow::Kill => Err(anyhow::anyhow!(
+                "Discarding from the right operand of `&&` or `||` is not supported"
+            )),
+            _ => Err(no_result(module, function)),
+        }
+    }
+
+    fn condition(
+        &mut self,
+        module: &Module,
+        expr: naga::Handle<naga::Expression>,
+        func: &naga::Function,
+    ) -> anyhow::Result<bool> {
+        match self.expression(module, expr, func)? {
+            Value::Scalar(Scalar::Bool(condition)) => Ok(condition),
+            value => Err(anyhow::anyhow!(
+                "Invalid condition: expected bool, got {:?}",
+                value.ty(module)
+            )),
+        }
+    }
+}
+
+fn no_result(module: &Module, function: Handle<Function>) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Function `{}` did not return a value",
+        module.functions[function]
+            .name
+            .as_deref()
+            .unwrap_or("<unnamed>")
+    )
+}
+
+/// The arguments of the calls in `block` that produce a result, by their
+/// result.
+pub(super) fn calls(block: &Block) -> FxHashMap<Handle<Expression>, Vec<Handle<Expression>>> {
+    let mut calls = FxHashMap::default();
+    let mut blocks = vec![block];
+    while let Some(block) = blocks.pop() {
+        for stmt in block.iter() {
+            match *stmt {
+                Statement::Block(ref block) => blocks.push(block),
+                Statement::If {
+                    ref accept,
+                    ref reject,
+                    ..
+                } => blocks.extend([accept, reject]),
+                Statement::Switch { ref cases, .. } => {
+                    blocks.extend(cases.iter().map(|case| &case.body))
+                }
+                Statement::Loop {
+                    ref body,
+                    ref continuing,
+                    ..
+                } => blocks.extend([body, continuing]),
+                Statement::Call {
+                    ref arguments,
+                    result: Some(result),
+                    ..
+                } => {
+                    calls.insert(result, arguments.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+    calls
+}
+
+/// The expressions of `func` that only the right operand of a `&&` or `||`
+/// depends on, including the results of calls. naga emits them, and runs the
+/// calls, alongside everything else, but they must only be evaluated if the
+/// left operand does not decide the result.
+pub(super) fn deferred(
+    func: &naga::Function,
+    calls: &FxHashMap<Handle<Expression>, Vec<Handle<Expression>>>,
+) -> FxHashSet<Handle<Expression>> {
+    let is_logical = |op| matches!(op, BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr);
+    // a call result depends on the arguments of its call
+    let operands = |expr: Handle<Expression>| {
+        let mut operands = operands(&func.expressions[expr]);
+        operands.extend(calls.get(&expr).into_iter().flatten());
+        operands
+    };
+
+    // everything the right operands depend on
+    let mut candidates = FxHashSet::default();
+    let mut stack = func
+        .expressions
+        .iter()
+        .filter_map(|(_, expr)| match *expr {
+            Expression::Binary { op, right, .. } if is_logical(op) => Some(right),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    while let Some(expr) = stack.pop() {
+        if candidates.insert(expr) {
+            stack.extend(operands(expr));
+        }
+    }
+
+    // minus what is needed regardless: the other expressions and `let`
+    // bindings, along with their operands other than right operands
+    let mut needed = FxHashSet::default();
+    let mut stack = func
+        .expressions
+        .iter()
+        .map(|(expr, _)| expr)
+        .filter(|expr| !candidates.contains(expr) || func.named_expressions.contains_key(expr))
+        .collect::<Vec<_>>();
+    while let Some(expr) = stack.pop() {
+        if !needed.insert(expr) {
+            continue;
+        }
+        match func.expressions[expr] {
+            Expression::Binary { op, left, .. } if is_logical(op) => stack.push(left),
+            _ => stack.extend(operands(expr)),
+        }
+    }
+    candidates.retain(|expr| !needed.contains(expr));
+    candidates
+}
+
+/// The subexpressions `expr` reads.
+fn operands(expr: &Expression) -> Vec<Handle<Expression>> {
+    match *expr {
+        Expression::Literal(_)
+        | Expression::Constant(_)
+        | Expression::ZeroValue(_)
+        | Expression::FunctionArgument(_)
+        | Expression::GlobalVariable(_)
+        | Expression::LocalVariable(_)
+        | Expression::CallResult(_)
+        | Expression::AtomicResult { .. }
+        | Expression::WorkGroupUniformLoadResult { .. }
+        | Expression::RayQueryProceedResult => vec![],
+        Expression::Compose { ref components, .. } => components.clone(),
+        Expression::Access { base, index } => vec![base, index],
+        Expression::AccessIndex { base, .. } => vec![base],
+        Expression::Splat { value, .. } => vec![value],
+        Expression::Swizzle { vector, .. } => vec![vector],
+        Expression::Load { pointer } => vec![pointer],
+        Expression::ImageSample {
+            image,
+            sampler,
+            coordinate,
+            array_index,
+            offset,
+            level,
+            depth_ref,
+            ..
+        } => {
+            let level = match level {
+                naga::SampleLevel::Auto | naga::SampleLevel::Zero => vec![],
+                naga::SampleLevel::Exact(level) | naga::SampleLevel::Bias(level) => vec![level],
+                naga::SampleLevel::Gradient { x, y } => vec![x, y],
+            };
+            [image, sampler, coordinate]
+                .into_iter()
+                .chain(array_index)
+                .chain(offset)
+                .chain(depth_ref)
+                .chain(level)
+                .collect()
+        }
+        Expression::ImageLoad {
+            image,
+            coordinate,
+            array_index,
+            sample,
+            level,
+        } => [image, coordinate]
+            .into_iter()
+            .chain(array_index)
+            .chain(sample)
+            .chain(level)
+            .collect(),
+        Expression::ImageQuery { image, query } => match query {
+            naga::ImageQuery::Size { level: Some(level) } => vec![image, level],
+            _ => vec![image],
+        },
+        Expression::Unary { expr, .. } => vec![expr],
+        Expression::Binary { left, right, .. } => vec![left, right],
+        Expression::Select {
+            condition,
+            accept,
+            reject,
+        } => vec![condition, accept, reject],
+        Expression::Derivative { expr, .. } => vec![expr],
+        Expression::Relational { argument, .. } => vec![argument],
+        Expression::Math {
+            arg,
+            arg1,
+            arg2,
+            arg3,
+            ..
+        } => [Some(arg), arg1, arg2, arg3]
+            .into_iter()
+            .flatten()
+            .collect(),
+        Expression::As { expr, .. } => vec![expr],
+        Expression::ArrayLength(expr) => vec![expr],
+        Expression::RayQueryGetIntersection { query, .. } => vec![query],
+    }
 }
@@ -1,110 +1,581 @@
-use naga::TypeInner;
+use naga::{
+    AddressSpace, ArraySize, Bytes, GlobalVariable, Handle, LocalVariable, Module, ScalarKind,
+    Type, TypeInner, VectorSize,
+};
 
-#[derive(Clone, Debug)]
-pub struct Value<'a> {
-    pub ty: &'a TypeInner,
-    pub data: Vec<u8>,
+use super::binding_name;
+
+/// Distance in bytes between the columns of a matrix. Columns are aligned like
+/// vectors, so three-row columns are padded to four components.
+pub fn column_stride(rows: VectorSize, width: Bytes) -> usize {
+    let rows = match rows {
+        VectorSize::Bi => 2,
+        VectorSize::Tri | VectorSize::Quad => 4,
+    };
+    rows * width as usize
+}
+
+/// Number of elements in an array with the given size and stride, where a
+/// runtime-sized array has as many elements as fit in `bytes`.
+pub fn array_length(size: ArraySize, stride: u32, bytes: usize) -> usize {
+    match size {
+        ArraySize::Constant(size) => size.get() as usize,
+        ArraySize::Dynamic => bytes / stride as usize,
+    }
+}
+
+/// Splits a scalar or vector type into its vector size (`None` for scalars),
+/// scalar kind and width.
+pub fn scalar_shape(ty: &TypeInner) -> Option<(Option<VectorSize>, ScalarKind, Bytes)> {
+    match *ty {
+        TypeInner::Scalar { kind, width } => Some((None, kind, width)),
+        TypeInner::Vector { size, kind, width } => Some((Some(size), kind, width)),
+        _ => None,
+    }
 }
 
-impl<'a> Value<'a> {
-    pub fn from_data(ty: &'a TypeInner, data: Vec<u8>) -> Self {
-        Self { ty, data }
+/// The inverse of [`scalar_shape`].
+pub fn shaped_type(size: Option<VectorSize>, kind: ScalarKind, width: Bytes) -> TypeInner {
+    match size {
+        Some(size) => TypeInner::Vector { size, kind, width },
+        None => TypeInner::Scalar { kind, width },
     }
+}
+
+/// The size of a vector with `len` components.
+pub fn vector_size(len: usize) -> anyhow::Result<VectorSize> {
+    match len {
+        2 => Ok(VectorSize::Bi),
+        3 => Ok(VectorSize::Tri),
+        4 => Ok(VectorSize::Quad),
+        len => Err(anyhow::anyhow!("Invalid vector of {} components", len)),
+    }
+}
 
-    pub fn from_pod<T: bytemuck::Pod>(ty: &'a TypeInner, value: T) -> Self {
-        Self {
-            ty,
-            data: bytemuck::bytes_of(&value).to_vec(),
+/// A single scalar component.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Scalar {
+    Bool(bool),
+    I32(i32),
+    U32(u32),
+    F32(f32),
+    F64(f64),
+}
+
+impl Scalar {
+    pub fn kind(self) -> ScalarKind {
+        match self {
+            Self::Bool(_) => ScalarKind::Bool,
+            Self::I32(_) => ScalarKind::Sint,
+            Self::U32(_) => ScalarKind::Uint,
+            Self::F32(_) | Self::F64(_) => ScalarKind::Float,
         }
     }
 
-    pub fn try_get<T: bytemuck::Pod>(&self) -> anyhow::Result<&T> {
-        let value = bytemuck::try_from_bytes(&self.data).map_err(|_| {
-            anyhow::anyhow!("Invalid type: expected {:?}", std::any::type_name::<T>())
-        })?;
-        Ok(value)
+    pub fn width(self) -> Bytes {
+        match self {
+            Self::Bool(_) => 1,
+            Self::I32(_) | Self::U32(_) | Self::F32(_) => 4,
+            Self::F64(_) => 8,
+        }
     }
 
-    pub fn try_get_mut<T: bytemuck::Pod>(&mut self) -> anyhow::Result<&mut T> {
-        let value = bytemuck::try_from_bytes_mut(&mut self.data).map_err(|_| {
-            anyhow::anyhow!("Invalid type: expected {:?}", std::any::type_name::<T>())
-        })?;
-        Ok(value)
+    pub fn zero(kind: ScalarKind, width: Bytes) -> anyhow::Result<Self> {
+        Self::from_bytes(kind, width, &[0; 8])
     }
 
-    pub fn try_get_offset<T: bytemuck::Pod>(&self, offset: usize) -> anyhow::Result<&T> {
-        let value = bytemuck::try_from_bytes(&self.data[offset..offset + std::mem::size_of::<T>()])
-            .map_err(|_| {
-                anyhow::anyhow!("Invalid type: expected {:?}", std::any::type_name::<T>())
-            })?;
-        Ok(value)
+    /// Reads a scalar of the given kind and width from the start of `bytes`.
+    pub fn from_bytes(kind: ScalarKind, width: Bytes, bytes: &[u8]) -> anyhow::Result<Self> {
+        let bytes = bytes.get(..width as usize).ok_or(anyhow::anyhow!(
+            "Invalid scalar: expected {} bytes, got {}",
+            width,
+            bytes.len()
+        ))?;
+        let scalar = match (kind, width) {
+            (ScalarKind::Bool, 1) => Self::Bool(bytes[0] != 0),
+            (ScalarKind::Sint, 4) => Self::I32(bytemuck::pod_read_unaligned(bytes)),
+            (ScalarKind::Uint, 4) => Self::U32(bytemuck::pod_read_unaligned(bytes)),
+            (ScalarKind::Float, 4) => Self::F32(bytemuck::pod_read_unaligned(bytes)),
+            (ScalarKind::Float, 8) => Self::F64(bytemuck::pod_read_unaligned(bytes)),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported scalar: {:?} of width {}",
+                    kind,
+                    width
+                ))
+            }
+        };
+        Ok(scalar)
     }
 
-    pub fn try_get_offset_mut<T: bytemuck::Pod>(
-        &mut self,
-        offset: usize,
-    ) -> anyhow::Result<&mut T> {
-        let value =
-            bytemuck::try_from_bytes_mut(&mut self.data[offset..offset + std::mem::size_of::<T>()])
-                .map_err(|_| {
-                    anyhow::anyhow!("Invalid type: expected {:?}", std::any::type_name::<T>())
-                })?;
-        Ok(value)
+    pub fn to_bytes(self) -> Vec<u8> {
+        match self {
+            Self::Bool(value) => vec![value as u8],
+            Self::I32(value) => bytemuck::bytes_of(&value).to_vec(),
+            Self::U32(value) => bytemuck::bytes_of(&value).to_vec(),
+            Self::F32(value) => bytemuck::bytes_of(&value).to_vec(),
+            Self::F64(value) => bytemuck::bytes_of(&value).to_vec(),
+        }
     }
 
-    pub fn try_display(&self) -> anyhow::Result<String> {
-        match self.ty {
-            TypeInner::Scalar { kind, width } => match kind {
-                naga::ScalarKind::Sint => match width {
-                    4 => Ok(self.try_get::<i32>()?.to_string()),
-                    _ => todo!("{:?}", width),
-                },
-                naga::ScalarKind::Uint => match width {
-                    4 => Ok(self.try_get::<u32>()?.to_string()),
-                    _ => todo!("{:?}", width),
-                },
-                naga::ScalarKind::Float => match width {
-                    4 => Ok(self.try_get::<f32>()?.to_string()),
-                    _ => todo!("{:?}", width),
+    pub fn parse(kind: ScalarKind, width: Bytes, value: &str) -> anyhow::Result<Self> {
+        let value = value.trim();
+        let scalar = match (kind, width) {
+            (ScalarKind::Bool, 1) => Self::Bool(value.parse()?),
+            (ScalarKind::Sint, 4) => Self::I32(value.parse()?),
+            (ScalarKind::Uint, 4) => Self::U32(value.parse()?),
+            (ScalarKind::Float, 4) => Self::F32(value.parse()?),
+            (ScalarKind::Float, 8) => Self::F64(value.parse()?),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Unsupported scalar: {:?} of width {}",
+                    kind,
+                    width
+                ))
+            }
+        };
+        Ok(scalar)
+    }
+}
+
+impl std::fmt::Display for Scalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", *value as u8),
+            Self::I32(value) => write!(f, "{}", value),
+            Self::U32(value) => write!(f, "{}", value),
+            Self::F32(value) => write!(f, "{}", value),
+            Self::F64(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The variable a pointer refers to.
+///
+/// Arguments need no variant of their own: WGSL arguments are immutable, so
+/// the only pointers to pass are to caller variables, and a pointer argument
+/// is the caller's own `Local` or `Global` pointer, frame index included.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PointerTarget {
+    /// A local variable of the frame at index `frame` in the call stack.
+    Local {
+        frame: usize,
+        local: Handle<LocalVariable>,
+    },
+    Global(Handle<GlobalVariable>),
+}
+
+/// A reference to a variable, or to a component within one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pointer {
+    pub target: PointerTarget,
+    /// The type of the target variable.
+    pub ty: Handle<Type>,
+    /// Indices of the components to follow from the variable down to the
+    /// referenced value.
+    pub path: Vec<u32>,
+    pub space: AddressSpace,
+}
+
+impl Pointer {
+    /// The pointer type, following `path` through the variable's type.
+    pub fn ty(&self, module: &Module) -> TypeInner {
+        let mut base = self.ty;
+        let mut path = self.path.iter();
+        while let Some(&index) = path.next() {
+            match module.types[base].inner {
+                TypeInner::Struct { ref members, .. } => match members.get(index as usize) {
+                    Some(member) => base = member.ty,
+                    None => break,
                 },
-                naga::ScalarKind::Bool => Ok(self.try_get::<u8>()?.to_string()),
+                TypeInner::Array { base: element, .. } => base = element,
+                TypeInner::Vector { kind, width, .. } => {
+                    return TypeInner::ValuePointer {
+                        size: None,
+                        kind,
+                        width,
+                        space: self.space,
+                    }
+                }
+                TypeInner::Matrix { rows, width, .. } => {
+                    return TypeInner::ValuePointer {
+                        size: path.next().map_or(Some(rows), |_| None),
+                        kind: ScalarKind::Float,
+                        width,
+                        space: self.space,
+                    }
+                }
+                _ => break,
+            }
+        }
+        TypeInner::Pointer {
+            base,
+            space: self.space,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Scalar(Scalar),
+    /// The components of a vector, all of the same kind and width.
+    Vector(Vec<Scalar>),
+    /// The columns of a matrix, each holding one float per row.
+    Matrix(Vec<Vec<Scalar>>),
+    /// The members of a struct or the elements of an array of type `ty`.
+    Composite {
+        ty: Handle<Type>,
+        components: Vec<Value>,
+    },
+    Pointer(Pointer),
+}
+
+impl Value {
+    /// The type of the value.
+    pub fn ty(&self, module: &Module) -> TypeInner {
+        match *self {
+            Self::Scalar(scalar) => TypeInner::Scalar {
+                kind: scalar.kind(),
+                width: scalar.width(),
+            },
+            Self::Vector(ref components) => TypeInner::Vector {
+                size: vector_size(components.len()).unwrap_or(VectorSize::Quad),
+                kind: components[0].kind(),
+                width: components[0].width(),
+            },
+            Self::Matrix(ref columns) => TypeInner::Matrix {
+                columns: vector_size(columns.len()).unwrap_or(VectorSize::Quad),
+                rows: vector_size(columns[0].len()).unwrap_or(VectorSize::Quad),
+                width: columns[0][0].width(),
+            },
+            Self::Composite { ty, .. } => module.types[ty].inner.clone(),
+            Self::Pointer(ref pointer) => pointer.ty(module),
+        }
+    }
+
+    /// Reads a value of type `ty` from its byte layout in a buffer. A
+    /// runtime-sized array takes as many elements as fit in `bytes`.
+    pub fn from_bytes(module: &Module, ty: Handle<Type>, bytes: &[u8]) -> anyhow::Result<Self> {
+        let inner = &module.types[ty].inner;
+        let slice = |offset: usize| {
+            bytes.get(offset..).ok_or(anyhow::anyhow!(
+                "Invalid offset {} into a {}-byte value",
+                offset,
+                bytes.len()
+            ))
+        };
+        let value = match *inner {
+            // atomics are stored as plain scalars
+            TypeInner::Scalar { kind, width } | TypeInner::Atomic { kind, width } => {
+                Self::Scalar(Scalar::from_bytes(kind, width, bytes)?)
+            }
+            TypeInner::Vector { size, kind, width } => Self::Vector(
+                (0..size as usize)
+                    .map(|i| Scalar::from_bytes(kind, width, slice(i * width as usize)?))
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            TypeInner::Matrix {
+                columns,
+                rows,
+                width,
+            } => {
+                let stride = column_stride(rows, width);
+                Self::Matrix(
+                    (0..columns as usize)
+                        .map(|column| {
+                            (0..rows as usize)
+                                .map(|row| {
+                                    let offset = column * stride + row * width as usize;
+                                    Scalar::from_bytes(ScalarKind::Float, width, slice(offset)?)
+                                })
+                                .collect()
+                        })
+                        .collect::<anyhow::Result<_>>()?,
+                )
+            }
+            TypeInner::Struct { ref members, .. } => Self::Composite {
+                ty,
+                components: members
+                    .iter()
+                    .map(|member| {
+                        Self::from_bytes(module, member.ty, slice(member.offset as usize)?)
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            },
+            TypeInner::Array { base, size, stride } => Self::Composite {
+                ty,
+                components: (0..array_length(size, stride, bytes.len()))
+                    .map(|i| Self::from_bytes(module, base, slice(i * stride as usize)?))
+                    .collect::<anyhow::Result<_>>()?,
             },
-            TypeInner::Vector { size, kind, width } => {
-                let size = *size as usize;
-                let width = *width as usize;
-                let mut result = String::default();
-                for i in 0..size {
-                    match kind {
-                        naga::ScalarKind::Sint => match width {
-                            4 => {
-                                let value = self.try_get_offset::<i32>(i * width)?;
-                                result += &format!("{}, ", value);
-                            }
-                            _ => todo!("{:?}", width),
-                        },
-                        naga::ScalarKind::Uint => match width {
-                            4 => {
-                                let value = self.try_get_offset::<u32>(i * width)?;
-                                result += &format!("{}, ", value);
-                            }
-                            _ => todo!("{:?}", width),
-                        },
-                        naga::ScalarKind::Float => match width {
-                            4 => {
-                                let value = self.try_get_offset::<f32>(i * width)?;
-                                result += &format!("{}, ", value);
-                            }
-                            _ => todo!("{:?}", width),
-                        },
-                        naga::ScalarKind::Bool => {
-                            let value = self.try_get_offset::<u8>(i * width)?;
-                            result += &format!("{}, ", value);
+            ref ty => return Err(anyhow::anyhow!("Unsupported value type: {:?}", ty)),
+        };
+        Ok(value)
+    }
+
+    /// Lays the value out as it would be stored in a buffer, the inverse of
+    /// [`Value::from_bytes`].
+    pub fn to_bytes(&self, module: &Module) -> anyhow::Result<Vec<u8>> {
+        let bytes = match *self {
+            Self::Scalar(scalar) => scalar.to_bytes(),
+            Self::Vector(ref components) => components.iter().flat_map(|c| c.to_bytes()).collect(),
+            Self::Matrix(ref columns) => {
+                let width = columns[0][0].width();
+                let stride = column_stride(vector_size(columns[0].len())?, width);
+                let mut bytes = vec![0; columns.len() * stride];
+                for (i, column) in columns.iter().enumerate() {
+                    let column = column.iter().flat_map(|c| c.to_bytes()).collect::<Vec<_>>();
+                    bytes[i * stride..i * stride + column.len()].copy_from_slice(&column);
+                }
+                bytes
+            }
+            Self::Composite { ty, ref components } => {
+                let (offsets, size) = match module.types[ty].inner {
+                    TypeInner::Struct {
+                        ref members, span, ..
+                    } => (
+                        members.iter().map(|m| m.offset as usize).collect(),
+                        span as usize,
+                    ),
+                    TypeInner::Array { stride, .. } => {
+                        let stride = stride as usize;
+                        (
+                            (0..components.len())
+                                .map(|i| i * stride)
+                                .collect::<Vec<_>>(),
+                            components.len() * stride,
+                        )
+                    }
+                    ref ty => return Err(anyhow::anyhow!("Invalid composite type: {:?}", ty)),
+                };
+                let mut bytes = vec![0; size];
+                for (offset, component) in offsets.into_iter().zip(components) {
+                    let component = component.to_bytes(module)?;
+                    // a runtime-sized array member extends past the struct's span
+                    if bytes.len() < offset + component.len() {
+                        bytes.resize(offset + component.len(), 0);
+                    }
+                    bytes[offset..offset + component.len()].copy_from_slice(&component);
+                }
+                bytes
+            }
+            Self::Pointer(_) => {
+                return Err(anyhow::anyhow!(
+                    "Pointers cannot be stored in a buffer: {:?}",
+                    self.ty(module)
+                ))
+            }
+        };
+        Ok(bytes)
+    }
+
+    /// The zero value of `ty`, as produced by `ZeroValue` and uninitialized
+    /// variables.
+    pub fn zeroed(module: &Module, ty: Handle<Type>) -> anyhow::Result<Self> {
+        let size = module.types[ty].inner.size(module.to_ctx()) as usize;
+        Self::from_bytes(module, ty, &vec![0; size])
+    }
+
+    /// Builds a scalar from a single component, or a vector from several.
+    pub fn from_components(components: Vec<Scalar>) -> anyhow::Result<Self> {
+        match components.len() {
+            1 => Ok(Self::Scalar(components[0])),
+            len => {
+                vector_size(len)?;
+                Ok(Self::Vector(components))
+            }
+        }
+    }
+
+    /// The components of a scalar or vector.
+    pub fn try_components(&self) -> anyhow::Result<&[Scalar]> {
+        match self {
+            Self::Scalar(scalar) => Ok(std::slice::from_ref(scalar)),
+            Self::Vector(components) => Ok(components),
+            value => Err(anyhow::anyhow!(
+                "Expected a scalar or vector, got {:?}",
+                value
+            )),
+        }
+    }
+
+    pub fn try_scalar(&self) -> anyhow::Result<Scalar> {
+        match *self {
+            Self::Scalar(scalar) => Ok(scalar),
+            ref value => Err(anyhow::anyhow!("Expected a scalar, got {:?}", value)),
+        }
+    }
+
+    pub fn try_bool(&self) -> anyhow::Result<bool> {
+        match *self {
+            Self::Scalar(Scalar::Bool(value)) => Ok(value),
+            ref value => Err(anyhow::anyhow!("Expected bool, got {:?}", value)),
+        }
+    }
+
+    pub fn try_u32(&self) -> anyhow::Result<u32> {
+        match *self {
+            Self::Scalar(Scalar::U32(value)) => Ok(value),
+            ref value => Err(anyhow::anyhow!("Expected u32, got {:?}", value)),
+        }
+    }
+
+    /// Applies `f` to each component of a scalar or vector, keeping its shape.
+    pub fn try_map(&self, f: impl FnMut(Scalar) -> anyhow::Result<Scalar>) -> anyhow::Result<Self> {
+        let components = self
+            .try_components()?
+            .iter()
+            .copied()
+            .map(f)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::from_components(components)
+    }
+
+    /// Repeats a scalar value into a vector of `size` components.
+    pub fn try_splat(&self, size: VectorSize) -> anyhow::Result<Self> {
+        let scalar = match *self {
+            Self::Scalar(scalar) => scalar,
+            ref value => return Err(anyhow::anyhow!("Invalid splat of {:?}", value)),
+        };
+        Ok(Self::Vector(vec![scalar; size as usize]))
+    }
+
+    /// Number of components that can be indexed, or `None` for a scalar.
+    fn len(&self) -> Option<usize> {
+        match self {
+            Self::Scalar(_) | Self::Pointer(_) => None,
+            Self::Vector(components) => Some(components.len()),
+            Self::Matrix(columns) => Some(columns.len()),
+            Self::Composite { components, .. } => Some(components.len()),
+        }
+    }
+
+    fn check_index(&self, module: &Module, index: u32) -> anyhow::Result<usize> {
+        let length = self
+            .len()
+            .ok_or(anyhow::anyhow!("Cannot index into {:?}", self.ty(module)))?;
+        if index as usize >= length {
+            return Err(anyhow::anyhow!(
+                "Index {} is out of bounds for {:?} of length {}",
+                index,
+                self.ty(module),
+                length
+            ));
+        }
+        Ok(index as usize)
+    }
+
+    /// Returns component `index` of a vector, column `index` of a matrix, or
+    /// member or element `index` of a composite.
+    pub fn try_component(&self, module: &Module, index: u32) -> anyhow::Result<Self> {
+        let index = self.check_index(module, index)?;
+        let component = match *self {
+            Self::Vector(ref components) => Self::Scalar(components[index]),
+            Self::Matrix(ref columns) => Self::Vector(columns[index].clone()),
+            Self::Composite { ref components, .. } => components[index].clone(),
+            Self::Scalar(_) | Self::Pointer(_) => unreachable!(),
+        };
+        Ok(component)
+    }
+
+    /// Follows a path of component indices, as produced by a pointer.
+    pub fn try_load(&self, module: &Module, path: &[u32]) -> anyhow::Result<Self> {
+        match path.split_first() {
+            None => Ok(self.clone()),
+            Some((&index, rest)) => match *self {
+                Self::Composite { ref components, .. } => {
+                    let index = self.check_index(module, index)?;
+                    components[index].try_load(module, rest)
+                }
+                _ => self.try_component(module, index)?.try_load(module, rest),
+            },
+        }
+    }
+
+    /// Replaces the component at the end of `path` with `value`, which must
+    /// have the same type.
+    pub fn try_store(&mut self, module: &Module, path: &[u32], value: Self) -> anyhow::Result<()> {
+        let Some((&index, rest)) = path.split_first() else {
+            if self.ty(module) != value.ty(module) {
+                return Err(anyhow::anyhow!(
+                    "Invalid store: expected {:?}, got {:?}",
+                    self.ty(module),
+                    value.ty(module)
+                ));
+            }
+            *self = value;
+            return Ok(());
+        };
+        let index = self.check_index(module, index)?;
+        match *self {
+            Self::Vector(ref mut components) => {
+                let mut component = Self::Scalar(components[index]);
+                component.try_store(module, rest, value)?;
+                components[index] = component.try_scalar()?;
+            }
+            Self::Matrix(ref mut columns) => {
+                let mut column = Self::Vector(columns[index].clone());
+                column.try_store(module, rest, value)?;
+                columns[index] = column.try_components()?.to_vec();
+            }
+            Self::Composite {
+                ref mut components, ..
+            } => components[index].try_store(module, rest, value)?,
+            Self::Scalar(_) | Self::Pointer(_) => unreachable!(),
+        }
+        Ok(())
+    }
+
+    pub fn try_display(&self, module: &Module) -> anyhow::Result<String> {
+        let list = |items: Vec<String>| format!("[{}]", items.join(", "));
+        match *self {
+            Self::Scalar(scalar) => Ok(scalar.to_string()),
+            Self::Vector(ref components) => {
+                Ok(list(components.iter().map(|c| c.to_string()).collect()))
+            }
+            Self::Matrix(ref columns) => Ok(list(
+                columns
+                    .iter()
+                    .map(|column| list(column.iter().map(|c| c.to_string()).collect()))
+                    .collect(),
+            )),
+            Self::Composite { ty, ref components } => match module.types[ty].inner {
+                TypeInner::Struct { ref members, .. } => {
+                    let mut result = String::default();
+                    for (index, (member, value)) in members.iter().zip(components).enumerate() {
+                        if let Some(binding) = member.binding.as_ref() {
+                            result += &format!("{} ", binding_name(binding));
+                        }
+                        match member.name.as_ref() {
+                            Some(name) => result += &format!("{}: ", name),
+                            None => result += &format!("{}: ", index),
                         }
+                        result += &format!("{}, ", value.try_display(module)?);
                     }
+                    Ok(format!("{{ {} }}", result.trim_end_matches(", ")))
                 }
-                Ok(format!("[{}]", result.trim_end_matches(", ")))
+                _ => Ok(list(
+                    components
+                        .iter()
+                        .map(|c| c.try_display(module))
+                        .collect::<anyhow::Result<_>>()?,
+                )),
+            },
+            Self::Pointer(ref pointer) => {
+                let target = match pointer.target {
+                    PointerTarget::Local { local, .. } => format!("local {}", local.index()),
+                    PointerTarget::Global(global) => match module.global_variables[global].name {
+                        Some(ref name) => name.to_owned(),
+                        None => format!("{:?}", global),
+                    },
+                };
+                let path = pointer
+                    .path
+                    .iter()
+                    .map(|index| format!("[{}]", index))
+                    .collect::<String>();
+                Ok(format!("&{}{} ({:?})", target, path, pointer.space))
             }
-            _ => todo!("{:?}", self.ty),
         }
     }
 }
@@ -20,6 +20,10 @@ struct Args {
     mode: Mode,
     #[clap(short, long, default_value = "0")]
     verbosity: u8,
+    /// JSON or RON file describing the contents of the shader's uniform and
+    /// storage buffers
+    #[clap(short, long)]
+    bindings: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -36,12 +40,11 @@ fn main() -> anyhow::Result<()> {
 
     let mut interpreter = interpreter::Interpreter::new(stage, args.verbosity);
 
-    match path.extension() {
+    let module: naga::Module = match path.extension() {
         Some(ext) if ext == "wgsl" => {
             #[cfg(feature = "wgsl")]
             {
-                let module = naga::front::wgsl::parse_str(&source)?;
-                interpreter.run(&module)?;
+                naga::front::wgsl::parse_str(&source)?
             }
             #[cfg(not(feature = "wgsl"))]
             {
@@ -51,8 +54,9 @@ fn main() -> anyhow::Result<()> {
         Some(ext) if ext == "frag" || ext == "vert" => {
             #[cfg(feature = "glsl")]
             {
-                let module = naga::front::glsl::parse_str(&source, stage)?;
-                interpreter.run(module)?;
+                naga::front::glsl::Frontend::default()
+                    .parse(&stage.into(), &source)
+                    .map_err(|errors| anyhow::anyhow!("Invalid GLSL: {:?}", errors))?
             }
             #[cfg(not(feature = "glsl"))]
             {
@@ -62,7 +66,13 @@ fn main() -> anyhow::Result<()> {
         _ => {
             return Err(anyhow::anyhow!("Unsupported file extension"));
         }
+    };
+
+    interpreter.load_module(&module)?;
+    if let Some(bindings) = args.bindings.as_deref() {
+        interpreter.bind_manifest(&module, bindings)?;
     }
+    interpreter.run(&module)?;
 
     Ok(())
 }
@@ -0,0 +1,48 @@
+mod common;
+
+use common::run_fixture;
+
+#[test]
+fn scalar_vector_broadcast() {
+    assert_eq!(
+        run_fixture("binary/broadcast.wgsl", "2, 4, 6\n3\n"),
+        "[12, 24, 36]"
+    );
+    assert_eq!(
+        run_fixture("binary/broadcast_int.wgsl", "2, -4\n3\n"),
+        "[6, -12]"
+    );
+}
+
+#[test]
+fn float_comparisons() {
+    let fixture = "binary/compare_float.wgsl";
+    assert_eq!(run_fixture(fixture, "1.5\n2\n"), "[1, 1, 0, 1]");
+    assert_eq!(run_fixture(fixture, "2\n2\n"), "[0, 1, 0, 0]");
+    assert_eq!(run_fixture(fixture, "NaN\n2\n"), "[0, 0, 0, 1]");
+}
+
+#[test]
+fn vector_comparisons() {
+    assert_eq!(
+        run_fixture("binary/compare_vector.wgsl", "1, 2, -3\n2, 2, -4\n"),
+        "[0, 1, 1]"
+    );
+}
+
+#[test]
+fn logical_operators_short_circuit() {
+    let fixture = "binary/logical.wgsl";
+    assert_eq!(run_fixture(fixture, "0\n1\n"), "[1, 1]");
+    assert_eq!(run_fixture(fixture, "1\n0\n"), "[0, 0]");
+    assert_eq!(run_fixture(fixture, "5\n7\n"), "[0, 1]");
+    assert_eq!(run_fixture(fixture, "0\n2\n"), "[1, 1]");
+}
+
+#[test]
+fn logical_operators_skip_calls_in_right_operands() {
+    let fixture = "binary/logical_calls.wgsl";
+    assert_eq!(run_fixture(fixture, "0\n1\n"), "[0, 0, 2]");
+    assert_eq!(run_fixture(fixture, "2\n0\n"), "[2, 5, 2]");
+    assert_eq!(run_fixture(fixture, "7\n1\n"), "[4, 0, 3]");
+}
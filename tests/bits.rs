@@ -0,0 +1,154 @@
+mod common;
+
+use common::run_fixture;
+
+const COUNT_ONE_BITS: u32 = 0;
+const COUNT_LEADING_ZEROS: u32 = 1;
+const COUNT_TRAILING_ZEROS: u32 = 2;
+const FIRST_LEADING_BIT: u32 = 3;
+const FIRST_TRAILING_BIT: u32 = 4;
+const REVERSE_BITS: u32 = 5;
+const EXTRACT_BITS: u32 = 6;
+const INSERT_BITS: u32 = 7;
+
+/// `(op, e, newbits, offset, count, expected)`
+type Case<T> = (u32, [T; 2], [T; 2], u32, u32, [T; 2]);
+
+fn check<T: std::fmt::Display>(fixture: &str, cases: &[Case<T>]) {
+    for (op, e, newbits, offset, count, expected) in cases {
+        let input = format!(
+            "{}\n{},{}\n{},{}\n{}\n{}\n",
+            op, e[0], e[1], newbits[0], newbits[1], offset, count
+        );
+        assert_eq!(
+            run_fixture(fixture, &input),
+            format!("[{}, {}]", expected[0], expected[1]),
+            "op {} with e = {}, {}, newbits = {}, {}, offset = {}, count = {}",
+            op,
+            e[0],
+            e[1],
+            newbits[0],
+            newbits[1],
+            offset,
+            count
+        );
+    }
+}
+
+#[test]
+fn u32_bits() {
+    check::<u32>(
+        "math/bits_u32.wgsl",
+        &[
+            (COUNT_ONE_BITS, [u32::MAX, 0xa0], [0; 2], 0, 0, [32, 2]),
+            (COUNT_LEADING_ZEROS, [0, 1], [0; 2], 0, 0, [32, 31]),
+            (COUNT_TRAILING_ZEROS, [0, 0x80], [0; 2], 0, 0, [32, 7]),
+            (
+                FIRST_LEADING_BIT,
+                [0, 0x8000_0001],
+                [0; 2],
+                0,
+                0,
+                [u32::MAX, 31],
+            ),
+            (FIRST_TRAILING_BIT, [0, 0x30], [0; 2], 0, 0, [u32::MAX, 4]),
+            (
+                REVERSE_BITS,
+                [1, 0xf0],
+                [0; 2],
+                0,
+                0,
+                [0x8000_0000, 0x0f00_0000],
+            ),
+            (EXTRACT_BITS, [0xabcd, u32::MAX], [0; 2], 4, 8, [0xbc, 0xff]),
+            (EXTRACT_BITS, [0xabcd, u32::MAX], [0; 2], 28, 8, [0, 0xf]),
+            (
+                EXTRACT_BITS,
+                [0xabcd, u32::MAX],
+                [0; 2],
+                0,
+                32,
+                [0xabcd, u32::MAX],
+            ),
+            (EXTRACT_BITS, [0xabcd, u32::MAX], [0; 2], 40, 8, [0, 0]),
+            (
+                INSERT_BITS,
+                [0, u32::MAX],
+                [0x1ff, 0],
+                4,
+                8,
+                [0xff0, 0xffff_f00f],
+            ),
+            (INSERT_BITS, [0, u32::MAX], [0x1ff, 0], 0, 0, [0, u32::MAX]),
+            (INSERT_BITS, [0, u32::MAX], [0x1ff, 0], 0, 32, [0x1ff, 0]),
+        ],
+    );
+}
+
+#[test]
+fn i32_bits() {
+    check::<i32>(
+        "math/bits_i32.wgsl",
+        &[
+            (COUNT_ONE_BITS, [-1, 5], [0; 2], 0, 0, [32, 2]),
+            (COUNT_LEADING_ZEROS, [-1, 1], [0; 2], 0, 0, [0, 31]),
+            (COUNT_TRAILING_ZEROS, [0, -8], [0; 2], 0, 0, [32, 3]),
+            (FIRST_LEADING_BIT, [0, -1], [0; 2], 0, 0, [-1, -1]),
+            (FIRST_LEADING_BIT, [0x40, -96], [0; 2], 0, 0, [6, 6]),
+            (FIRST_TRAILING_BIT, [0, -8], [0; 2], 0, 0, [-1, 3]),
+            (REVERSE_BITS, [1, -1], [0; 2], 0, 0, [i32::MIN, -1]),
+            (EXTRACT_BITS, [-96, 0x70], [0; 2], 4, 4, [-6, 7]),
+            (EXTRACT_BITS, [-96, 0x30], [0; 2], 4, 3, [2, 3]),
+            (EXTRACT_BITS, [-96, 0x30], [0; 2], 4, 0, [0, 0]),
+            (INSERT_BITS, [-1, -96], [0, 5], 4, 8, [-4081, -4016]),
+        ],
+    );
+}
+
+#[test]
+fn packing() {
+    let cases = [
+        (0, "1,-1,0.5,0", 0x0040_817f_u32),
+        (0, "2,-2,-0.5,0.25", 0x20c1_817f),
+        (1, "1,0,0.5,2", 0xff80_00ff),
+        (2, "1,-1,0,0", 0x8001_7fff),
+        (2, "-0.5,0.5,0,0", 0x4000_c001),
+        (3, "1,0.5,0,0", 0x8000_ffff),
+        (4, "1,-1,0,0", 0xbc00_3c00),
+        (4, "65504,65520,0,0", 0x7c00_7bff),
+        (4, "0.00000005960464,-0.00006103515625,0,0", 0x8400_0001),
+        (4, "1.00048828125,0,0,0", 0x0000_3c00),
+    ];
+    for (op, v, expected) in cases {
+        assert_eq!(
+            run_fixture("math/pack.wgsl", &format!("{}\n{}\n", op, v)),
+            expected.to_string(),
+            "op {} with v = {}",
+            op,
+            v
+        );
+    }
+}
+
+#[test]
+fn unpacking() {
+    let cases = [
+        (0, 0x0040_817f_u32, "[1, -1, 0.503937, 0]"),
+        (0, 0x0000_0080, "[-1, 0, 0, 0]"),
+        (1, 0xff80_00ff, "[1, 0, 0.5019608, 1]"),
+        (2, 0x8001_7fff, "[1, -1, 0, 0]"),
+        (3, 0x0000_ffff, "[1, 0, 0, 0]"),
+        (4, 0xbc00_3c00, "[1, -1, 0, 0]"),
+        (4, 0x7c00_3555, "[0.33325195, inf, 0, 0]"),
+        (4, 0x8001_0400, "[0.000061035156, -0.000000059604645, 0, 0]"),
+    ];
+    for (op, e, expected) in cases {
+        assert_eq!(
+            run_fixture("math/unpack.wgsl", &format!("{}\n{}\n", op, e)),
+            expected,
+            "op {} with e = {:#x}",
+            op,
+            e
+        );
+    }
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use common::run_fixture;
+
+#[test]
+fn nested_helper_calls() {
+    assert_eq!(
+        run_fixture("calls/helpers.wgsl", "1, 2, 3\n"),
+        "[5.5, 8.5, 11.5]"
+    );
+}
+
+#[test]
+fn arguments_bind_by_position() {
+    assert_eq!(run_fixture("calls/argument_names.wgsl", "1\n3\n"), "18");
+}
+
+#[test]
+fn pointer_arguments_write_through_to_the_caller() {
+    assert_eq!(
+        run_fixture("calls/pointer_arguments.wgsl", "1, 2, 3\n"),
+        "[2, 5, 6, 4]"
+    );
+}
+
+#[test]
+fn return_call_result_directly() {
+    assert_eq!(run_fixture("calls/return_call.wgsl", "1.5\n"), "6");
+}
@@ -0,0 +1,91 @@
+use std::{
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+fn fixture_path(fixture: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(fixture)
+}
+
+/// Runs the interpreter on `tests/fixtures/<fixture>` as a vertex shader,
+/// answering the argument prompts with `input`.
+fn run(fixture: &str, input: &str) -> Output {
+    run_with_bindings(fixture, None, input)
+}
+
+/// Like [`run`], passing `tests/fixtures/<bindings>` as the bindings manifest.
+fn run_with_bindings(fixture: &str, bindings: Option<&str>, input: &str) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_shader-loom"));
+    command.arg(fixture_path(fixture));
+    if let Some(bindings) = bindings {
+        command.arg("--bindings").arg(fixture_path(bindings));
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn shader-loom");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Runs a fixture and returns everything printed after `Result: `.
+pub fn run_fixture(fixture: &str, input: &str) -> String {
+    let output = run(fixture, input);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{} failed:\n{}\n{}",
+        fixture,
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Result: "))
+        .unwrap_or_else(|| panic!("{} produced no result:\n{}", fixture, stdout))
+        .to_owned()
+}
+
+/// Runs a fixture that is expected to fail and returns the reported error.
+#[allow(dead_code)]
+pub fn run_fixture_error(fixture: &str, input: &str) -> String {
+    let output = run(fixture, input);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !output.status.success(),
+        "{} unexpectedly succeeded:\n{}",
+        fixture,
+        String::from_utf8_lossy(&output.stdout)
+    );
+    stderr
+        .lines()
+        .find_map(|line| line.strip_prefix("Error: "))
+        .unwrap_or_else(|| panic!("{} reported no error:\n{}", fixture, stderr))
+        .to_owned()
+}
+
+/// Runs a fixture with a bindings manifest and returns its whole output.
+#[allow(dead_code)]
+pub fn run_fixture_with_bindings(fixture: &str, bindings: &str, input: &str) -> String {
+    let output = run_with_bindings(fixture, Some(bindings), input);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{} failed:\n{}\n{}",
+        fixture,
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    stdout.into_owned()
+}
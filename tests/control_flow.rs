@@ -0,0 +1,71 @@
+mod common;
+
+use common::run_fixture;
+
+#[test]
+fn return_from_nested_blocks() {
+    assert_eq!(
+        run_fixture("control_flow/nested_block_return.wgsl", ""),
+        "1"
+    );
+}
+
+#[test]
+fn return_from_if_branches() {
+    assert_eq!(run_fixture("control_flow/if_return.wgsl", "true\n"), "1");
+    assert_eq!(run_fixture("control_flow/if_return.wgsl", "false\n"), "2");
+}
+
+#[test]
+fn return_from_loop_body() {
+    assert_eq!(run_fixture("control_flow/loop_return.wgsl", ""), "1");
+}
+
+#[test]
+fn return_after_loop_break() {
+    assert_eq!(
+        run_fixture("control_flow/loop_break_return.wgsl", "true\n"),
+        "2"
+    );
+    assert_eq!(
+        run_fixture("control_flow/loop_break_return.wgsl", "false\n"),
+        "1"
+    );
+}
+
+#[test]
+fn return_after_continue() {
+    assert_eq!(
+        run_fixture("control_flow/continuing_return.wgsl", "true\n"),
+        "2"
+    );
+    assert_eq!(
+        run_fixture("control_flow/continuing_return.wgsl", "false\n"),
+        "1"
+    );
+}
+
+#[test]
+fn switch_cases() {
+    let fixture = "control_flow/switch.wgsl";
+    assert_eq!(run_fixture(fixture, "0\n"), "10");
+    assert_eq!(run_fixture(fixture, "1\n"), "20");
+    assert_eq!(run_fixture(fixture, "2\n"), "20");
+    assert_eq!(run_fixture(fixture, "3\n"), "40");
+    assert_eq!(run_fixture(fixture, "-5\n"), "30");
+}
+
+#[test]
+fn switch_unsigned_selector() {
+    let fixture = "control_flow/switch_unsigned.wgsl";
+    assert_eq!(run_fixture(fixture, "7\n"), "2");
+    assert_eq!(run_fixture(fixture, "8\n"), "1");
+}
+
+#[test]
+fn switch_inside_loop() {
+    let fixture = "control_flow/switch_in_loop.wgsl";
+    assert_eq!(run_fixture(fixture, "0\n"), "2");
+    assert_eq!(run_fixture(fixture, "1\n"), "1");
+    assert_eq!(run_fixture(fixture, "5\n"), "3");
+}
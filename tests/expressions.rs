@@ -0,0 +1,112 @@
+mod common;
+
+use common::{run_fixture, run_fixture_error};
+
+#[test]
+fn unary_operators() {
+    assert_eq!(
+        run_fixture("expressions/unary.wgsl", "1.5, -2\n7\n15\nfalse\n"),
+        "[-1.5, 2, -7, 1]"
+    );
+}
+
+#[test]
+fn select_scalar_and_vector_conditions() {
+    let fixture = "expressions/select.wgsl";
+    assert_eq!(
+        run_fixture(fixture, "1, 5, 3\n2, 4, 6\ntrue\n"),
+        "[1, 4, 3]"
+    );
+    assert_eq!(
+        run_fixture(fixture, "1, 5, 3\n2, 4, 6\nfalse\n"),
+        "[1, 5, 3]"
+    );
+}
+
+#[test]
+fn value_conversions() {
+    let fixture = "expressions/convert.wgsl";
+    assert_eq!(
+        run_fixture(fixture, "3e10, -7.9\n-0\n5\n"),
+        "[2147483647, -7, 2, 1]"
+    );
+    assert_eq!(
+        run_fixture(fixture, "NaN, -3e10\n2\n2\n"),
+        "[0, -2147483648, 1, 1]"
+    );
+}
+
+#[test]
+fn bitcasts() {
+    assert_eq!(
+        run_fixture("expressions/bitcast.wgsl", "1, -2\n-1\n"),
+        "[1065353216, 3221225472, 4294967295]"
+    );
+}
+
+#[test]
+fn swizzle_and_splat() {
+    assert_eq!(
+        run_fixture("expressions/swizzle.wgsl", "1, 2, 3, 4\n"),
+        "[7, 6, 5, 2]"
+    );
+}
+
+#[test]
+fn access_values_and_pointers() {
+    let fixture = "expressions/access.wgsl";
+    let m = "[[1, 2, 3], [4, 5, 6], [7, 8, 9]]";
+    assert_eq!(
+        run_fixture(fixture, &format!("{}\n1\n2\n", m)),
+        "[5, 5, 10, 9]"
+    );
+    assert_eq!(
+        run_fixture_error(fixture, &format!("{}\n3\n2\n", m)),
+        "Index 3 is out of bounds for Matrix { columns: Tri, rows: Tri, width: 4 } of length 3"
+    );
+    assert_eq!(
+        run_fixture_error(fixture, &format!("{}\n-1\n2\n", m)),
+        "Index -1 is out of bounds"
+    );
+}
+
+#[test]
+fn any_and_all() {
+    let fixture = "expressions/relational.wgsl";
+    assert_eq!(run_fixture(fixture, "1, 2, 3\n2, 3, 4\n"), "[1, 1]");
+    assert_eq!(run_fixture(fixture, "1, 5, 3\n2, 3, 4\n"), "[1, 0]");
+    assert_eq!(run_fixture(fixture, "3, 5, 7\n2, 3, 4\n"), "[0, 0]");
+}
+
+#[test]
+#[cfg(feature = "glsl")]
+fn is_nan_and_is_inf() {
+    let fixture = "expressions/nan_inf.vert";
+    assert_eq!(
+        run_fixture(fixture, "NaN, inf\n"),
+        "{ @location(0) result: [1, 0, 0, 1] }"
+    );
+    assert_eq!(
+        run_fixture(fixture, "-inf, 1\n"),
+        "{ @location(0) result: [0, 0, 1, 0] }"
+    );
+}
+
+#[test]
+fn constants_and_zero_values() {
+    assert_eq!(
+        run_fixture("expressions/constants.wgsl", "1, 1, 1\n"),
+        "[3, 4, 5, 2]"
+    );
+}
+
+#[test]
+fn arrays() {
+    let fixture = "expressions/arrays.wgsl";
+    assert_eq!(run_fixture(fixture, "1\n0\n"), "[2, 0.25, 2, 4]");
+    assert_eq!(run_fixture(fixture, "2\n1\n"), "[5, 0.5, 3, 4]");
+    assert_eq!(
+        run_fixture_error(fixture, "0\n2\n"),
+        "Index 2 is out of bounds for Array { base: [3], size: Constant(2), stride: 16 } of length 2"
+    );
+}
@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(v: vec3<f32>, s: f32) -> @location(0) vec3<f32> {
+    return v * s + s * v;
+}
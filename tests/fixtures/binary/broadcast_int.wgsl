@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(v: vec2<i32>, s: i32) -> @location(0) vec2<i32> {
+    return v * s;
+}
@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(a: f32, b: f32) -> @location(0) vec4<bool> {
+    return vec4<bool>(a < b, a <= b, a > b, a != b);
+}
@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(a: vec3<i32>, b: vec3<i32>) -> @location(0) vec3<bool> {
+    return a >= b;
+}
@@ -0,0 +1,26 @@
+@vertex
+fn vs_main(op: u32, a: i32, b: i32, amount: u32) -> @location(0) i32 {
+    switch op {
+        case 0u: {
+            return a + b;
+        }
+        case 1u: {
+            return a - b;
+        }
+        case 2u: {
+            return a * b;
+        }
+        case 3u: {
+            return a / b;
+        }
+        case 4u: {
+            return a % b;
+        }
+        case 5u: {
+            return a << amount;
+        }
+        default: {
+            return a >> amount;
+        }
+    }
+}
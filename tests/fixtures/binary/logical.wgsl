@@ -0,0 +1,6 @@
+@vertex
+fn vs_main(i: i32, j: i32) -> @location(0) vec2<bool> {
+    var a = array<i32, 2>(4, -1);
+    // the right operands index out of bounds unless the left ones guard them
+    return vec2<bool>(i < 2 && a[i] > 0, j >= 2 || a[j] < 0);
+}
@@ -0,0 +1,24 @@
+var<private> counter: u32 = 0u;
+
+fn bump() -> bool {
+    counter += 1u;
+    return true;
+}
+
+fn write(p: ptr<function, i32>, value: i32) -> bool {
+    *p = value;
+    return value > 0;
+}
+
+@vertex
+fn vs_main(a: u32, b: u32) -> @location(0) vec3<u32> {
+    // the calls in the right operands only run if the left ones do not
+    // decide the result
+    var r = false;
+    for (var i = 0u; i < 4u; i++) {
+        r = i < a && bump();
+    }
+    var x = 0;
+    let s = b != 0u || write(&x, 5);
+    return vec3<u32>(counter, u32(x), select(0u, 1u, r) + select(0u, 2u, s));
+}
@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(a: vec2<i32>, amount: vec2<u32>) -> @location(0) vec2<i32> {
+    return (a << amount) >> vec2<u32>(1u, 1u);
+}
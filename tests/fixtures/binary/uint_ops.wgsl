@@ -0,0 +1,26 @@
+@vertex
+fn vs_main(op: u32, a: u32, b: u32, amount: u32) -> @location(0) u32 {
+    switch op {
+        case 0u: {
+            return a + b;
+        }
+        case 1u: {
+            return a - b;
+        }
+        case 2u: {
+            return a * b;
+        }
+        case 3u: {
+            return a / b;
+        }
+        case 4u: {
+            return a % b;
+        }
+        case 5u: {
+            return a << amount;
+        }
+        default: {
+            return a >> amount;
+        }
+    }
+}
@@ -0,0 +1,9 @@
+fn pick(b: f32, a: f32) -> f32 {
+    var a_local = a;
+    return a_local - b;
+}
+
+@vertex
+fn vs_main(a: f32, b: f32) -> @location(0) f32 {
+    return pick(a, b) * 10.0 + pick(b, a);
+}
@@ -0,0 +1,17 @@
+fn offset(v: vec3<f32>, by: f32) -> vec3<f32> {
+    var result = v;
+    result = result + vec3<f32>(by, by, by);
+    return result;
+}
+
+fn scale(v: vec3<f32>) -> vec3<f32> {
+    let shifted = offset(v, 1.0);
+    return shifted + shifted;
+}
+
+@vertex
+fn vs_main(pos: vec3<f32>) -> @location(0) vec3<f32> {
+    var result = pos;
+    result = scale(result);
+    return offset(result, 0.5) + pos;
+}
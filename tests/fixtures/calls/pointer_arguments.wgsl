@@ -0,0 +1,28 @@
+struct Particle {
+    position: vec3<f32>,
+    velocity: vec3<f32>,
+}
+
+fn step(particle: ptr<function, Particle>, dt: f32) {
+    (*particle).position += (*particle).velocity * dt;
+}
+
+fn bump(value: ptr<function, f32>) {
+    *value = *value + 1.0;
+}
+
+fn swap(a: ptr<function, i32>, b: ptr<function, i32>) {
+    let t = *a;
+    *a = *b;
+    *b = t;
+}
+
+@vertex
+fn vs_main(velocity: vec3<f32>) -> @location(0) vec4<f32> {
+    var particle = Particle(vec3<f32>(0.0, 0.0, 0.0), velocity);
+    step(&particle, 2.0);
+    bump(&particle.position.y);
+    var counts = array<i32, 2>(3, 7);
+    swap(&counts[0], &counts[1]);
+    return vec4<f32>(particle.position, f32(counts[0] - counts[1]));
+}
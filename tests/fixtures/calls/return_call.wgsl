@@ -0,0 +1,12 @@
+fn double(x: f32) -> f32 {
+    return x * 2.0;
+}
+
+fn quadruple(x: f32) -> f32 {
+    return double(double(x));
+}
+
+@vertex
+fn vs_main(a: f32) -> @location(0) f32 {
+    return quadruple(a);
+}
@@ -0,0 +1,13 @@
+@vertex
+fn vs_main(skip: bool) -> @location(0) f32 {
+    loop {
+        if skip {
+            continue;
+        }
+        return 1.0;
+        continuing {
+            break if skip;
+        }
+    }
+    return 2.0;
+}
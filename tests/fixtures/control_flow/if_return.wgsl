@@ -0,0 +1,11 @@
+@vertex
+fn vs_main(take: bool) -> @location(0) f32 {
+    if take {
+        return 1.0;
+    } else {
+        {
+            return 2.0;
+        }
+    }
+    return 3.0;
+}
@@ -0,0 +1,10 @@
+@vertex
+fn vs_main(leave: bool) -> @location(0) f32 {
+    loop {
+        if leave {
+            break;
+        }
+        return 1.0;
+    }
+    return 2.0;
+}
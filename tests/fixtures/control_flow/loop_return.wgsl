@@ -0,0 +1,12 @@
+@vertex
+fn vs_main() -> @location(0) f32 {
+    loop {
+        {
+            if true {
+                return 1.0;
+            }
+        }
+        return 2.0;
+    }
+    return 3.0;
+}
@@ -0,0 +1,18 @@
+@vertex
+fn vs_main(material: i32) -> @location(0) f32 {
+    switch material {
+        case 0: {
+            return 10.0;
+        }
+        case 1, 2: {
+            return 20.0;
+        }
+        case 3: {
+            break;
+        }
+        default: {
+            return 30.0;
+        }
+    }
+    return 40.0;
+}
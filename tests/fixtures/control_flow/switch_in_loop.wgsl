@@ -0,0 +1,21 @@
+@vertex
+fn vs_main(selector: i32) -> @location(0) f32 {
+    loop {
+        switch selector {
+            case 0: {
+                break;
+            }
+            case 1: {
+                return 1.0;
+            }
+            default: {
+                continue;
+            }
+        }
+        return 2.0;
+        continuing {
+            break if true;
+        }
+    }
+    return 3.0;
+}
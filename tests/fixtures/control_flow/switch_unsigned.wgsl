@@ -0,0 +1,14 @@
+@vertex
+fn vs_main(material: u32) -> @location(0) f32 {
+    switch material {
+        default {
+            return 1.0;
+        }
+        case 7u: {
+            if true {
+                return 2.0;
+            }
+            return 3.0;
+        }
+    }
+}
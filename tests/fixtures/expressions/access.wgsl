@@ -0,0 +1,15 @@
+struct Light {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+@vertex
+fn vs_main(m: mat3x3<f32>, column: i32, row: u32) -> @location(0) vec4<f32> {
+    var light: Light;
+    light.color = m[column];
+    light.color[row] = 10.0;
+    light.intensity = m[2][row];
+    var v = vec4<f32>(light.color, light.intensity);
+    v.x += 1.0;
+    return v;
+}
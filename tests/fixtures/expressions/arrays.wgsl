@@ -0,0 +1,23 @@
+struct Light {
+    position: vec3<f32>,
+    intensity: f32,
+}
+
+const LUT = array<f32, 4>(0.0, 0.25, 0.5, 1.0);
+
+fn lights() -> array<Light, 2> {
+    return array<Light, 2>(
+        Light(vec3<f32>(1.0, 0.0, 0.0), 2.0),
+        Light(vec3<f32>(0.0, 4.0, 0.0), 3.0),
+    );
+}
+
+@vertex
+fn vs_main(i: u32, j: i32) -> @location(0) vec4<f32> {
+    var values = array<vec3<f32>, 3>();
+    values[1] = vec3<f32>(1.0, 2.0, 3.0);
+    values[2].y = 5.0;
+    let ls = lights();
+    let lut = LUT;
+    return vec4<f32>(values[i].y, lut[i], ls[j].intensity, ls[1].position.y);
+}
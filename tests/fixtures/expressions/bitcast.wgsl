@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(f: vec2<f32>, i: i32) -> @location(0) vec3<u32> {
+    return vec3<u32>(bitcast<vec2<u32>>(f), bitcast<u32>(i));
+}
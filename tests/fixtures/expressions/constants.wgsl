@@ -0,0 +1,17 @@
+struct Light {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+const SCALE: f32 = 2.0;
+const OFFSET = vec3<f32>(1.0, 2.0, 3.0);
+const ORIGIN = vec3<f32>();
+const LIGHT = Light(OFFSET, SCALE);
+const ONES = vec2<i32>(1);
+
+@vertex
+fn vs_main(v: vec3<f32>) -> @location(0) vec4<f32> {
+    let zero = vec4<f32>();
+    let scaled = v * LIGHT.intensity + LIGHT.color + ORIGIN;
+    return vec4<f32>(scaled, f32(ONES.x + ONES.y)) + zero;
+}
@@ -0,0 +1,5 @@
+@vertex
+fn vs_main(f: vec2<f32>, x: f32, u: u32) -> @location(0) vec4<i32> {
+    let from_bool = i32(u > 3u) + i32(u32(x != 0.0));
+    return vec4<i32>(vec2<i32>(f), i32(f32(u) * 0.5), from_bool);
+}
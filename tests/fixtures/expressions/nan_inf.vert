@@ -0,0 +1,9 @@
+#version 450
+
+layout(location = 0) in vec2 v;
+layout(location = 0) out vec4 result;
+
+// naga only emits `IsNan` and `IsInf` for GLSL
+void main() {
+    result = vec4(isnan(v), isinf(v));
+}
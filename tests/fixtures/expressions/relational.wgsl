@@ -0,0 +1,11 @@
+@vertex
+fn vs_main(a: vec3<f32>, b: vec3<f32>) -> @location(0) vec2<u32> {
+    var result = vec2<u32>(0u);
+    if (any(a < b)) {
+        result.x = 1u;
+    }
+    if (all(a < b)) {
+        result.y = 1u;
+    }
+    return result;
+}
@@ -0,0 +1,5 @@
+@vertex
+fn vs_main(a: vec3<f32>, b: vec3<f32>, pick: bool) -> @location(0) vec3<f32> {
+    let mixed = select(b, a, a < b);
+    return select(a, mixed, pick);
+}
@@ -0,0 +1,5 @@
+@vertex
+fn vs_main(v: vec4<f32>) -> @location(0) vec4<f32> {
+    let s = vec3<f32>(v.w);
+    return vec4<f32>(v.zyx + s, v.y);
+}
@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(f: vec2<f32>, i: i32, u: u32, b: bool) -> @location(0) vec4<f32> {
+    return vec4<f32>(-f, f32(-i), f32(~u == 0xfffffff0u && !b));
+}
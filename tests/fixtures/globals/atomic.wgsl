@@ -0,0 +1,12 @@
+struct Counters {
+    hits: atomic<u32>,
+    scale: u32,
+}
+
+@group(0) @binding(0)
+var<storage, read_write> counters: Counters;
+
+@vertex
+fn vs_main(x: u32) -> @location(0) u32 {
+    return x * counters.scale;
+}
@@ -0,0 +1,26 @@
+var<workgroup> counter: atomic<u32>;
+var<workgroup> low: atomic<i32>;
+
+@vertex
+fn vs_main(x: u32, y: i32) -> @location(0) vec4<i32> {
+    atomicStore(&counter, 5u);
+    let old = atomicAdd(&counter, x);
+    atomicSub(&counter, 1u);
+    atomicMax(&counter, 10u);
+    atomicOr(&counter, 0x100u);
+    atomicXor(&counter, 0x3u);
+    atomicAnd(&counter, 0x1feu);
+    let failed = atomicCompareExchangeWeak(&counter, 0u, 2u);
+    let exchanged = atomicCompareExchangeWeak(&counter, failed.old_value, 1u);
+
+    atomicMin(&low, y);
+    let previous = atomicExchange(&low, 7);
+
+    return vec4<i32>(
+        i32(old),
+        i32(failed.old_value),
+        previous + atomicLoad(&low),
+        select(0, 1, failed.exchanged) + select(0, 2, exchanged.exchanged)
+            + 10 * i32(atomicLoad(&counter)),
+    );
+}
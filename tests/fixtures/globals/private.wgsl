@@ -0,0 +1,14 @@
+var<private> counter: i32 = 10;
+var<private> total: vec2<f32>;
+
+fn tick(by: f32) {
+    counter += 1;
+    total += vec2<f32>(by, f32(counter));
+}
+
+@vertex
+fn vs_main(by: f32) -> @location(0) vec3<f32> {
+    tick(by);
+    tick(by);
+    return vec3<f32>(total, f32(counter));
+}
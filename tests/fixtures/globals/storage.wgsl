@@ -0,0 +1,18 @@
+struct Params {
+    offset: u32,
+    factor: u32,
+}
+
+@group(0) @binding(0)
+var<storage, read_write> data: array<u32>;
+
+@group(0) @binding(1)
+var<uniform> params: Params;
+
+@vertex
+fn vs_main(count: u32) -> @location(0) u32 {
+    for (var i = 0u; i < count; i++) {
+        data[i] = data[i] * params.factor + params.offset;
+    }
+    return arrayLength(&data);
+}
@@ -0,0 +1,11 @@
+struct Camera {
+    scale: f32,
+}
+
+@group(1) @binding(2)
+var<uniform> camera: Camera;
+
+@vertex
+fn vs_main(x: f32) -> @location(0) f32 {
+    return x * camera.scale;
+}
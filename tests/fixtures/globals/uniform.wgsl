@@ -0,0 +1,14 @@
+struct Camera {
+    mvp: mat4x4<f32>,
+    tint: vec3<f32>,
+    scale: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+@vertex
+fn vs_main(pos: vec3<f32>) -> @location(0) vec4<f32> {
+    let clip = camera.mvp * vec4<f32>(pos, 1.0) * camera.scale;
+    return clip + vec4<f32>(camera.tint, 0.0);
+}
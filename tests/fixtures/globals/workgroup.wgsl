@@ -0,0 +1,10 @@
+var<workgroup> counter: atomic<u32>;
+var<workgroup> tile: array<u32, 4>;
+
+@vertex
+fn vs_main(index: u32) -> @location(0) u32 {
+    tile[index] = index * 2u;
+    workgroupBarrier();
+    storageBarrier();
+    return tile[index] + 1u;
+}
@@ -0,0 +1,7 @@
+@vertex
+fn vs_main(x: f32) -> @location(0) f32 {
+    var a = x;
+    let old = a;
+    a = 100.0;
+    return old + a;
+}
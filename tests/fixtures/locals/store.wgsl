@@ -0,0 +1,8 @@
+@vertex
+fn vs_main(x: f32) -> @location(0) f32 {
+    var a: f32;
+    var b = 2.0;
+    a = x + b;
+    b = a + a;
+    return b;
+}
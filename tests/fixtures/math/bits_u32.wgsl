@@ -0,0 +1,14 @@
+@vertex
+fn vs_main(op: u32, e: vec2<u32>, newbits: vec2<u32>, offset: u32, count: u32) -> @location(0) vec2<u32> {
+    switch op {
+        case 0u: { return countOneBits(e); }
+        case 1u: { return countLeadingZeros(e); }
+        case 2u: { return countTrailingZeros(e); }
+        case 3u: { return firstLeadingBit(e); }
+        case 4u: { return firstTrailingBit(e); }
+        case 5u: { return reverseBits(e); }
+        case 6u: { return extractBits(e, offset, count); }
+        case 7u: { return insertBits(e, newbits, offset, count); }
+        default: { return vec2<u32>(0u); }
+    }
+}
@@ -0,0 +1,66 @@
+@vertex
+fn vs_main(op: u32, a: vec4<f32>, b: vec4<f32>, c: vec4<f32>) -> @location(0) vec4<f32> {
+    switch op {
+        case 0u: { return abs(a); }
+        case 1u: { return min(a, b); }
+        case 2u: { return max(a, b); }
+        case 3u: { return clamp(a, b, c); }
+        case 4u: { return saturate(a); }
+        case 5u: { return sin(a); }
+        case 6u: { return cos(a); }
+        case 7u: { return tan(a); }
+        case 8u: { return asin(a); }
+        case 9u: { return acos(a); }
+        case 10u: { return atan(a); }
+        case 11u: { return atan2(a, b); }
+        case 12u: { return sinh(a); }
+        case 13u: { return cosh(a); }
+        case 14u: { return tanh(a); }
+        case 15u: { return asinh(a); }
+        case 16u: { return acosh(a); }
+        case 17u: { return atanh(a); }
+        case 18u: { return radians(a); }
+        case 19u: { return degrees(a); }
+        case 20u: { return ceil(a); }
+        case 21u: { return floor(a); }
+        case 22u: { return round(a); }
+        case 23u: { return fract(a); }
+        case 24u: { return trunc(a); }
+        case 25u: { return exp(a); }
+        case 26u: { return exp2(a); }
+        case 27u: { return log(a); }
+        case 28u: { return log2(a); }
+        case 29u: { return pow(a, b); }
+        case 30u: { return sqrt(a); }
+        case 31u: { return inverseSqrt(a); }
+        case 32u: { return sign(a); }
+        case 33u: { return fma(a, b, c); }
+        case 34u: { return mix(a, b, c); }
+        case 35u: { return step(a, b); }
+        case 36u: { return smoothstep(a, b, c); }
+        case 37u: { return vec4<f32>(dot(a, b)); }
+        case 38u: { return vec4<f32>(cross(a.xyz, b.xyz), 0.0); }
+        case 39u: { return vec4<f32>(length(a)); }
+        case 40u: { return vec4<f32>(distance(a, b)); }
+        case 41u: { return normalize(a); }
+        case 42u: { return reflect(a, b); }
+        case 43u: { return refract(a, b, c.x); }
+        case 44u: { return faceForward(a, b, c); }
+        case 45u: { return modf(a).fract; }
+        case 46u: { return modf(a).whole; }
+        case 47u: { return frexp(a).fract; }
+        case 48u: { return vec4<f32>(frexp(a).exp); }
+        case 49u: { return ldexp(a, vec4<i32>(b)); }
+        case 50u: {
+            return vec4<f32>(
+                determinant(mat2x2<f32>(a.xy, a.zw)),
+                determinant(mat3x3<f32>(a.xyz, b.xyz, c.xyz)),
+                determinant(mat4x4<f32>(a, b, c, vec4<f32>(1.0, 2.0, 3.0, 5.0))),
+                0.0,
+            );
+        }
+        case 51u: { return transpose(mat4x4<f32>(a, b, c, vec4<f32>(0.0, 0.0, 0.0, 1.0)))[0]; }
+        case 52u: { return mix(a, b, c.x); }
+        default: { return vec4<f32>(0.0); }
+    }
+}
@@ -0,0 +1,12 @@
+@vertex
+fn vs_main(op: u32, a: vec4<i32>, b: vec4<i32>, c: vec4<i32>) -> @location(0) vec4<i32> {
+    switch op {
+        case 0u: { return abs(a); }
+        case 1u: { return min(a, b); }
+        case 2u: { return max(a, b); }
+        case 3u: { return clamp(a, b, c); }
+        case 32u: { return sign(a); }
+        case 37u: { return vec4<i32>(dot(a, b)); }
+        default: { return vec4<i32>(0); }
+    }
+}
@@ -0,0 +1,11 @@
+@vertex
+fn vs_main(op: u32, v: vec4<f32>) -> @location(0) u32 {
+    switch op {
+        case 0u: { return pack4x8snorm(v); }
+        case 1u: { return pack4x8unorm(v); }
+        case 2u: { return pack2x16snorm(v.xy); }
+        case 3u: { return pack2x16unorm(v.xy); }
+        case 4u: { return pack2x16float(v.xy); }
+        default: { return 0u; }
+    }
+}
@@ -0,0 +1,11 @@
+@vertex
+fn vs_main(op: u32, a: vec4<u32>, b: vec4<u32>, c: vec4<u32>) -> @location(0) vec4<u32> {
+    switch op {
+        case 0u: { return abs(a); }
+        case 1u: { return min(a, b); }
+        case 2u: { return max(a, b); }
+        case 3u: { return clamp(a, b, c); }
+        case 37u: { return vec4<u32>(dot(a, b)); }
+        default: { return vec4<u32>(0u); }
+    }
+}
@@ -0,0 +1,11 @@
+@vertex
+fn vs_main(op: u32, e: u32) -> @location(0) vec4<f32> {
+    switch op {
+        case 0u: { return unpack4x8snorm(e); }
+        case 1u: { return unpack4x8unorm(e); }
+        case 2u: { return vec4<f32>(unpack2x16snorm(e), 0.0, 0.0); }
+        case 3u: { return vec4<f32>(unpack2x16unorm(e), 0.0, 0.0); }
+        case 4u: { return vec4<f32>(unpack2x16float(e), 0.0, 0.0); }
+        default: { return vec4<f32>(0.0); }
+    }
+}
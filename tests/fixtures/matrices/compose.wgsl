@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(c: vec3<f32>) -> @location(0) mat3x3<f32> {
+    return mat3x3<f32>(c, vec3<f32>(4.0, 5.0, 6.0), c + c);
+}
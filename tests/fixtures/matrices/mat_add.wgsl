@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(a: mat3x3<f32>, b: mat3x3<f32>) -> @location(0) mat3x3<f32> {
+    return (a + b) - b * 2.0;
+}
@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(a: mat2x3<f32>, b: mat3x2<f32>) -> @location(0) mat3x3<f32> {
+    return a * b;
+}
@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(mvp: mat4x4<f32>, pos: vec3<f32>) -> @location(0) vec4<f32> {
+    return mvp * vec4<f32>(pos, 1.0);
+}
@@ -0,0 +1,4 @@
+@vertex
+fn vs_main(m: mat2x3<f32>, v: vec3<f32>) -> @location(0) vec2<f32> {
+    return v * m;
+}
@@ -0,0 +1,16 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) scale: f32,
+    @builtin(vertex_index) index: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+    @location(1) index: u32,
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    return VertexOutput(vec4<f32>(in.position, in.scale), in.position + in.position, in.index);
+}
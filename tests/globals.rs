@@ -0,0 +1,60 @@
+mod common;
+
+use common::{run_fixture, run_fixture_error, run_fixture_with_bindings};
+
+#[test]
+fn private_globals_start_from_their_initializers() {
+    assert_eq!(run_fixture("globals/private.wgsl", "1.5\n"), "[3, 23, 12]");
+}
+
+#[test]
+fn unbound_uniform_reports_its_binding() {
+    assert!(run_fixture_error("globals/unbound_uniform.wgsl", "2\n")
+        .contains("No buffer is bound to @group(1) @binding(2) for `camera`"));
+}
+
+#[test]
+fn uniform_buffer_from_json_manifest() {
+    let output =
+        run_fixture_with_bindings("globals/uniform.wgsl", "globals/uniform.json", "1, 1, 1\n");
+    assert!(
+        output.contains("Result: [12.5, 16.25, 16, 2]"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn storage_buffer_from_ron_manifest_is_written_back() {
+    let output = run_fixture_with_bindings("globals/storage.wgsl", "globals/storage.ron", "3\n");
+    assert!(output.contains("Result: 4"), "{}", output);
+    assert!(
+        output.contains("Buffer `data` @group(0) @binding(0): [13, 16, 19, 4]"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn workgroup_globals_and_barriers() {
+    assert_eq!(run_fixture("globals/workgroup.wgsl", "3\n"), "7");
+}
+
+#[test]
+fn atomic_builtins_read_modify_write() {
+    assert_eq!(
+        run_fixture("globals/atomics.wgsl", "3\n-4\n"),
+        "[5, 264, 3, 12]"
+    );
+}
+
+#[test]
+fn storage_struct_with_atomic_member_from_bin_file() {
+    let output = run_fixture_with_bindings("globals/atomic.wgsl", "globals/atomic.json", "4\n");
+    assert!(output.contains("Result: 12"), "{}", output);
+    assert!(
+        output.contains("Buffer `counters` @group(0) @binding(0): { hits: 5, scale: 3 }"),
+        "{}",
+        output
+    );
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::run_fixture;
+
+const ADD: u32 = 0;
+const SUB: u32 = 1;
+const MUL: u32 = 2;
+const DIV: u32 = 3;
+const MOD: u32 = 4;
+const SHL: u32 = 5;
+const SHR: u32 = 6;
+
+fn check<T: std::fmt::Display>(fixture: &str, cases: &[(u32, T, T, u32, T)]) {
+    for (op, a, b, amount, expected) in cases {
+        let input = format!("{}\n{}\n{}\n{}\n", op, a, b, amount);
+        assert_eq!(
+            run_fixture(fixture, &input),
+            expected.to_string(),
+            "op {} with a = {}, b = {}, amount = {}",
+            op,
+            a,
+            b,
+            amount
+        );
+    }
+}
+
+#[test]
+fn i32_conformance() {
+    check::<i32>(
+        "binary/int_ops.wgsl",
+        &[
+            (ADD, i32::MAX, 1, 0, i32::MIN),
+            (ADD, -5, 3, 0, -2),
+            (SUB, i32::MIN, 1, 0, i32::MAX),
+            (MUL, 65536, 65536, 0, 0),
+            (MUL, -3, 7, 0, -21),
+            (DIV, 7, 2, 0, 3),
+            (DIV, -7, 2, 0, -3),
+            (DIV, 5, 0, 0, 5),
+            (DIV, i32::MIN, -1, 0, i32::MIN),
+            (MOD, 7, 3, 0, 1),
+            (MOD, -7, 3, 0, -1),
+            (MOD, 5, 0, 0, 0),
+            (MOD, i32::MIN, -1, 0, 0),
+            (SHL, 1, 0, 31, i32::MIN),
+            (SHL, 1, 0, 33, 2),
+            (SHL, 3, 0, 32, 3),
+            (SHR, -8, 0, 1, -4),
+            (SHR, -8, 0, 35, -1),
+        ],
+    );
+}
+
+#[test]
+fn u32_conformance() {
+    check::<u32>(
+        "binary/uint_ops.wgsl",
+        &[
+            (ADD, u32::MAX, 1, 0, 0),
+            (SUB, 0, 1, 0, u32::MAX),
+            (MUL, u32::MAX, 2, 0, u32::MAX - 1),
+            (DIV, 7, 2, 0, 3),
+            (DIV, 7, 0, 0, 7),
+            (MOD, 7, 4, 0, 3),
+            (MOD, 7, 0, 0, 0),
+            (SHL, 1, 0, 32, 1),
+            (SHL, u32::MAX, 0, 4, 0xffff_fff0),
+            (SHR, u32::MAX, 0, 36, 0x0fff_ffff),
+        ],
+    );
+}
+
+#[test]
+fn vector_shifts() {
+    assert_eq!(
+        run_fixture("binary/shift_vector.wgsl", "-3, 5\n33, 2\n"),
+        "[-3, 10]"
+    );
+}
@@ -0,0 +1,13 @@
+mod common;
+
+use common::run_fixture;
+
+#[test]
+fn store_to_locals() {
+    assert_eq!(run_fixture("locals/store.wgsl", "3\n"), "10");
+}
+
+#[test]
+fn load_is_evaluated_when_emitted() {
+    assert_eq!(run_fixture("locals/load_before_store.wgsl", "3\n"), "103");
+}
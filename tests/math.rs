@@ -0,0 +1,317 @@
+mod common;
+
+use common::run_fixture;
+
+/// Ops of `tests/fixtures/math/float.wgsl`. `int.wgsl` and `uint.wgsl` share
+/// the numbering of the ops they support.
+const ABS: u32 = 0;
+const MIN: u32 = 1;
+const MAX: u32 = 2;
+const CLAMP: u32 = 3;
+const SATURATE: u32 = 4;
+const SIN: u32 = 5;
+const COS: u32 = 6;
+const TAN: u32 = 7;
+const ASIN: u32 = 8;
+const ACOS: u32 = 9;
+const ATAN: u32 = 10;
+const ATAN2: u32 = 11;
+const SINH: u32 = 12;
+const COSH: u32 = 13;
+const TANH: u32 = 14;
+const ASINH: u32 = 15;
+const ACOSH: u32 = 16;
+const ATANH: u32 = 17;
+const RADIANS: u32 = 18;
+const DEGREES: u32 = 19;
+const CEIL: u32 = 20;
+const FLOOR: u32 = 21;
+const ROUND: u32 = 22;
+const FRACT: u32 = 23;
+const TRUNC: u32 = 24;
+const EXP: u32 = 25;
+const EXP2: u32 = 26;
+const LOG: u32 = 27;
+const LOG2: u32 = 28;
+const POW: u32 = 29;
+const SQRT: u32 = 30;
+const INVERSE_SQRT: u32 = 31;
+const SIGN: u32 = 32;
+const FMA: u32 = 33;
+const MIX: u32 = 34;
+const STEP: u32 = 35;
+const SMOOTH_STEP: u32 = 36;
+const DOT: u32 = 37;
+const CROSS: u32 = 38;
+const LENGTH: u32 = 39;
+const DISTANCE: u32 = 40;
+const NORMALIZE: u32 = 41;
+const REFLECT: u32 = 42;
+const REFRACT: u32 = 43;
+const FACE_FORWARD: u32 = 44;
+const MODF_FRACT: u32 = 45;
+const MODF_WHOLE: u32 = 46;
+const FREXP_FRACT: u32 = 47;
+const FREXP_EXP: u32 = 48;
+const LDEXP: u32 = 49;
+const DETERMINANT: u32 = 50;
+const TRANSPOSE: u32 = 51;
+const MIX_SCALAR: u32 = 52;
+
+type Vec4 = [f64; 4];
+
+fn parse(result: &str) -> Vec<f64> {
+    result
+        .trim_matches(|c| c == '[' || c == ']')
+        .split(", ")
+        .map(|x| x.parse().unwrap())
+        .collect()
+}
+
+fn map(a: Vec4, f: fn(f64) -> f64) -> Vec4 {
+    a.map(f)
+}
+
+fn zip(a: Vec4, b: Vec4, f: fn(f64, f64) -> f64) -> Vec4 {
+    [0, 1, 2, 3].map(|i| f(a[i], b[i]))
+}
+
+fn dot(a: Vec4, b: Vec4) -> f64 {
+    (0..4).map(|i| a[i] * b[i]).sum()
+}
+
+fn format(v: Vec4) -> String {
+    v.map(|x| x.to_string()).join(",")
+}
+
+/// Runs every case through `tests/fixtures/math/float.wgsl` and compares the
+/// result against a reference computed in `f64`, with a tolerance for the
+/// rounding to `f32`.
+fn check(cases: &[(u32, Vec4, Vec4, Vec4, Vec4)]) {
+    for &(op, a, b, c, expected) in cases {
+        let input = format!("{}\n{}\n{}\n{}\n", op, format(a), format(b), format(c));
+        let result = parse(&run_fixture("math/float.wgsl", &input));
+        for (actual, expected) in result.iter().zip(expected) {
+            let tolerance = 1e-5 * expected.abs().max(1.0);
+            assert!(
+                (actual - expected).abs() <= tolerance || (actual.is_nan() && expected.is_nan()),
+                "op {} with a = {:?}, b = {:?}, c = {:?}: expected {:?}, got {:?}",
+                op,
+                a,
+                b,
+                c,
+                expected,
+                result
+            );
+        }
+    }
+}
+
+const A: Vec4 = [0.25, -0.5, 0.75, 0.9];
+const B: Vec4 = [2.0, 1.5, -3.0, 0.5];
+const C: Vec4 = [0.5, 0.0, 1.0, 0.25];
+const ZERO: Vec4 = [0.0; 4];
+
+#[test]
+fn componentwise() {
+    let positive = [1.5, 2.0, 10.0, 0.125];
+    check(&[
+        (ABS, A, ZERO, ZERO, map(A, f64::abs)),
+        (MIN, A, B, ZERO, zip(A, B, f64::min)),
+        (MAX, A, B, ZERO, zip(A, B, f64::max)),
+        (CLAMP, B, [0.0; 4], [1.0; 4], [1.0, 1.0, 0.0, 0.5]),
+        (SATURATE, B, ZERO, ZERO, [1.0, 1.0, 0.0, 0.5]),
+        (SIN, A, ZERO, ZERO, map(A, f64::sin)),
+        (COS, A, ZERO, ZERO, map(A, f64::cos)),
+        (TAN, A, ZERO, ZERO, map(A, f64::tan)),
+        (ASIN, A, ZERO, ZERO, map(A, f64::asin)),
+        (ACOS, A, ZERO, ZERO, map(A, f64::acos)),
+        (ATAN, A, ZERO, ZERO, map(A, f64::atan)),
+        (ATAN2, A, B, ZERO, zip(A, B, f64::atan2)),
+        (SINH, A, ZERO, ZERO, map(A, f64::sinh)),
+        (COSH, A, ZERO, ZERO, map(A, f64::cosh)),
+        (TANH, A, ZERO, ZERO, map(A, f64::tanh)),
+        (ASINH, A, ZERO, ZERO, map(A, f64::asinh)),
+        (ACOSH, positive, ZERO, ZERO, map(positive, f64::acosh)),
+        (ATANH, A, ZERO, ZERO, map(A, f64::atanh)),
+        (RADIANS, B, ZERO, ZERO, map(B, f64::to_radians)),
+        (DEGREES, B, ZERO, ZERO, map(B, f64::to_degrees)),
+        (CEIL, B, ZERO, ZERO, map(B, f64::ceil)),
+        (FLOOR, A, ZERO, ZERO, map(A, f64::floor)),
+        (
+            ROUND,
+            [0.5, 1.5, -2.5, 2.6],
+            ZERO,
+            ZERO,
+            [0.0, 2.0, -2.0, 3.0],
+        ),
+        (
+            FRACT,
+            [1.25, -1.25, 3.0, -0.5],
+            ZERO,
+            ZERO,
+            [0.25, 0.75, 0.0, 0.5],
+        ),
+        (
+            TRUNC,
+            [1.25, -1.75, 3.0, -0.5],
+            ZERO,
+            ZERO,
+            [1.0, -1.0, 3.0, 0.0],
+        ),
+        (EXP, A, ZERO, ZERO, map(A, f64::exp)),
+        (EXP2, B, ZERO, ZERO, map(B, f64::exp2)),
+        (LOG, positive, ZERO, ZERO, map(positive, f64::ln)),
+        (LOG2, positive, ZERO, ZERO, map(positive, f64::log2)),
+        (POW, positive, B, ZERO, zip(positive, B, f64::powf)),
+        (SQRT, positive, ZERO, ZERO, map(positive, f64::sqrt)),
+        (
+            INVERSE_SQRT,
+            positive,
+            ZERO,
+            ZERO,
+            map(positive, |x| 1.0 / x.sqrt()),
+        ),
+        (
+            SIGN,
+            [-2.0, 0.0, 3.0, -0.0],
+            ZERO,
+            ZERO,
+            [-1.0, 0.0, 1.0, 0.0],
+        ),
+        (FMA, A, B, C, [1.0, -0.75, -1.25, 0.7]),
+        (MIX, A, B, C, [1.125, -0.5, -3.0, 0.8]),
+        (STEP, A, B, ZERO, [1.0, 1.0, 0.0, 0.0]),
+        (
+            SMOOTH_STEP,
+            ZERO,
+            [1.0; 4],
+            [0.5, -1.0, 0.25, 2.0],
+            [0.5, 0.0, 0.15625, 1.0],
+        ),
+        (MIX_SCALAR, A, B, [0.5; 4], zip(A, B, |a, b| (a + b) / 2.0)),
+    ]);
+}
+
+#[test]
+fn geometric() {
+    let length = dot(A, A).sqrt();
+    let reflected = {
+        let d = 2.0 * dot(B, A);
+        [0, 1, 2, 3].map(|i| A[i] - d * B[i])
+    };
+    let n = [0.0, 1.0, 0.0, 0.0];
+    let i = [0.6, -0.8, 0.0, 0.0];
+    let eta = 0.5;
+    let refracted = {
+        let d = dot(n, i);
+        let k = 1.0 - eta * eta * (1.0 - d * d);
+        [0, 1, 2, 3].map(|j| eta * i[j] - (eta * d + f64::sqrt(k)) * n[j])
+    };
+    check(&[
+        (DOT, A, B, ZERO, [dot(A, B); 4]),
+        (
+            CROSS,
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            ZERO,
+            [0.0, 0.0, 1.0, 0.0],
+        ),
+        (
+            CROSS,
+            [1.0, 2.0, 3.0, 0.0],
+            [4.0, 5.0, 6.0, 0.0],
+            ZERO,
+            [-3.0, 6.0, -3.0, 0.0],
+        ),
+        (LENGTH, A, ZERO, ZERO, [length; 4]),
+        (
+            DISTANCE,
+            A,
+            B,
+            ZERO,
+            [dot(zip(A, B, |a, b| a - b), zip(A, B, |a, b| a - b)).sqrt(); 4],
+        ),
+        (NORMALIZE, A, ZERO, ZERO, A.map(|x| x / length)),
+        (REFLECT, A, B, ZERO, reflected),
+        (REFRACT, i, n, [eta; 4], refracted),
+        (REFRACT, [0.6, -0.8, 0.0, 0.0], n, [2.0; 4], ZERO),
+        (FACE_FORWARD, A, B, C, A),
+        (FACE_FORWARD, A, B, map(C, |x| -x), map(A, |x| -x)),
+    ]);
+}
+
+#[test]
+fn decomposition() {
+    let x = [1.5, -2.25, 8.0, 0.0];
+    check(&[
+        (MODF_FRACT, x, ZERO, ZERO, [0.5, -0.25, 0.0, 0.0]),
+        (MODF_WHOLE, x, ZERO, ZERO, [1.0, -2.0, 8.0, 0.0]),
+        (FREXP_FRACT, x, ZERO, ZERO, [0.75, -0.5625, 0.5, 0.0]),
+        (FREXP_EXP, x, ZERO, ZERO, [1.0, 2.0, 4.0, 0.0]),
+        (
+            LDEXP,
+            [0.75, -0.5625, 0.5, 3.0],
+            [1.0, 2.0, 4.0, -1.0],
+            ZERO,
+            [1.5, -2.25, 8.0, 1.5],
+        ),
+    ]);
+}
+
+#[test]
+fn matrices() {
+    let a = [1.0, 2.0, 3.0, 4.0];
+    let b = [0.0, 1.0, 4.0, 1.0];
+    let c = [5.0, 6.0, 0.0, 2.0];
+    check(&[
+        (DETERMINANT, a, b, c, [-2.0, 1.0, 1.0, 0.0]),
+        (TRANSPOSE, a, b, c, [1.0, 0.0, 5.0, 0.0]),
+    ]);
+}
+
+#[test]
+fn integers() {
+    assert_eq!(
+        run_fixture(
+            "math/int.wgsl",
+            &format!("{}\n-3,0,4,-2147483648\n0,0,0,0\n0,0,0,0\n", ABS)
+        ),
+        "[3, 0, 4, -2147483648]"
+    );
+    assert_eq!(
+        run_fixture(
+            "math/int.wgsl",
+            &format!("{}\n-5,0,5,10\n-1,-1,-1,-1\n1,1,1,1\n", CLAMP)
+        ),
+        "[-1, 0, 1, 1]"
+    );
+    assert_eq!(
+        run_fixture(
+            "math/int.wgsl",
+            &format!("{}\n-5,0,5,10\n0,0,0,0\n0,0,0,0\n", SIGN)
+        ),
+        "[-1, 0, 1, 1]"
+    );
+    assert_eq!(
+        run_fixture(
+            "math/int.wgsl",
+            &format!("{}\n1,2,3,4\n-1,2,-3,4\n0,0,0,0\n", DOT)
+        ),
+        "[10, 10, 10, 10]"
+    );
+    assert_eq!(
+        run_fixture(
+            "math/uint.wgsl",
+            &format!("{}\n1,7,3,9\n4,2,3,10\n0,0,0,0\n", MIN)
+        ),
+        "[1, 2, 3, 9]"
+    );
+    assert_eq!(
+        run_fixture(
+            "math/uint.wgsl",
+            &format!("{}\n1,2,3,4\n5,6,7,8\n0,0,0,0\n", DOT)
+        ),
+        "[70, 70, 70, 70]"
+    );
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::run_fixture;
+
+#[test]
+fn matrix_arguments() {
+    assert_eq!(
+        run_fixture("matrices/passthrough.wgsl", "[[1, 2, 3], [4, 5, 6]]\n"),
+        "[[1, 2, 3], [4, 5, 6]]"
+    );
+}
+
+#[test]
+fn compose_padded_columns() {
+    assert_eq!(
+        run_fixture("matrices/compose.wgsl", "1, 2, 3\n"),
+        "[[1, 2, 3], [4, 5, 6], [2, 4, 6]]"
+    );
+}
+
+#[test]
+fn translate_and_scale_point() {
+    // Scales by (2, 3, 4) and then translates by (10, 20, 30).
+    let mvp = "[[2, 0, 0, 0], [0, 3, 0, 0], [0, 0, 4, 0], [10, 20, 30, 1]]";
+    assert_eq!(
+        run_fixture("matrices/mat_vec.wgsl", &format!("{}\n1, 2, 3\n", mvp)),
+        "[12, 26, 42, 1]"
+    );
+}
+
+#[test]
+fn vector_times_matrix() {
+    // Each result component is the dot product of `v` with a column.
+    assert_eq!(
+        run_fixture(
+            "matrices/vec_mat.wgsl",
+            "[[1, 2, 3], [4, 5, 6]]\n1, 0, -1\n"
+        ),
+        "[-2, -2]"
+    );
+}
+
+#[test]
+fn matrix_times_matrix() {
+    // a = | 1 4 |   b = | 1 0 2 |   a * b = |  1  4  6 |
+    //     | 2 5 |       | 0 1 1 |           |  2  5  9 |
+    //     | 3 6 |                           |  3  6 12 |
+    assert_eq!(
+        run_fixture(
+            "matrices/mat_mat.wgsl",
+            "[[1, 2, 3], [4, 5, 6]]\n[[1, 0], [0, 1], [2, 1]]\n"
+        ),
+        "[[1, 2, 3], [4, 5, 6], [6, 9, 12]]"
+    );
+}
+
+#[test]
+fn componentwise_matrix_arithmetic() {
+    assert_eq!(
+        run_fixture(
+            "matrices/mat_add.wgsl",
+            "[[1, 2, 3], [4, 5, 6], [7, 8, 9]]\n[[1, 1, 1], [2, 2, 2], [3, 3, 3]]\n"
+        ),
+        "[[0, 1, 2], [2, 3, 4], [4, 5, 6]]"
+    );
+}
@@ -0,0 +1,12 @@
+mod common;
+
+use common::run_fixture;
+
+#[test]
+fn struct_entry_point_io() {
+    assert_eq!(
+        run_fixture("structs/vertex_io.wgsl", "1, 2, 3\n4\n7\n"),
+        "{ @builtin(position) clip_position: [1, 2, 3, 4], \
+         @location(0) color: [2, 4, 6], @location(1) index: 7 }"
+    );
+}